@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use wit_bindgen_core::{Files, WorldGenerator};
+use wit_parser::{Resolve, WorldId};
+
+/// Targets the `wit.generateBindings` command can produce, matching a
+/// subset of the `wit-bindgen` CLI's own `--generator` choices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Rust,
+    C,
+    Markdown,
+}
+
+impl FromStr for Target {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "rust" => Ok(Target::Rust),
+            "c" => Ok(Target::C),
+            "markdown" => Ok(Target::Markdown),
+            other => Err(anyhow!("unsupported bindings target `{other}`")),
+        }
+    }
+}
+
+fn generator_for(target: Target) -> Box<dyn WorldGenerator> {
+    match target {
+        Target::Rust => Box::new(wit_bindgen_rust::Opts::default().build()),
+        Target::C => Box::new(wit_bindgen_c::Opts::default().build()),
+        Target::Markdown => Box::new(wit_bindgen_markdown::Opts::default().build()),
+    }
+}
+
+/// Generates bindings for `world` in `resolve` using `target`, concatenating
+/// every file the generator produces (most targets only emit one) into a
+/// single string for `workspace/executeCommand` to hand back to the client.
+pub fn generate(resolve: &mut Resolve, world: WorldId, target: Target) -> Result<String> {
+    let mut generator = generator_for(target);
+    let mut files = Files::default();
+    generator.generate(resolve, world, &mut files)?;
+
+    let mut output = String::new();
+    for (name, contents) in files.iter() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("// {name}\n"));
+        output.push_str(&String::from_utf8_lossy(contents));
+    }
+
+    Ok(output)
+}
@@ -1,10 +1,15 @@
 use tower_lsp::lsp_types::{
+    CodeActionProviderCapability,
+    CompletionOptions,
+    ExecuteCommandOptions,
+    OneOf,
     SemanticTokensFullOptions,
     SemanticTokensOptions, SemanticTokensServerCapabilities, ServerCapabilities,
     TextDocumentSyncCapability, TextDocumentSyncKind, HoverProviderCapability, HoverOptions, WorkDoneProgressOptions,
 };
 
 use super::wit;
+use super::{FETCH_DEPENDENCIES_COMMAND, GENERATE_BINDINGS_COMMAND};
 
 pub fn server_capabilities() -> ServerCapabilities {
     ServerCapabilities {
@@ -14,16 +19,33 @@ pub fn server_capabilities() -> ServerCapabilities {
                 work_done_progress: Some(false),
             }
         })),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            resolve_provider: Some(false),
+            trigger_characters: Some(vec![".".to_owned(), "/".to_owned()]),
+            ..CompletionOptions::default()
+        }),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
         semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
             SemanticTokensOptions {
                 work_done_progress_options: Default::default(),
                 legend: wit::token::legend(),
                 full: Some(SemanticTokensFullOptions::Delta {
-                    delta: Some(false),
+                    delta: Some(true),
                 }),
-                range: None,
+                range: Some(true),
             },
         )),
+        execute_command_provider: Some(ExecuteCommandOptions {
+            commands: vec![
+                GENERATE_BINDINGS_COMMAND.to_owned(),
+                FETCH_DEPENDENCIES_COMMAND.to_owned(),
+            ],
+            work_done_progress_options: Default::default(),
+        }),
         ..ServerCapabilities::default()
     }
 }
\ No newline at end of file
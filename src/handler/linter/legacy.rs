@@ -0,0 +1,219 @@
+use lazy_regex::lazy_regex;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, Diagnostic, DiagnosticSeverity, Position, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+use std::collections::HashMap;
+
+/// A diagnostic paired with the [`CodeAction`] that migrates it to current
+/// package syntax.
+pub struct LegacyFix {
+    pub diagnostic: Diagnostic,
+    pub action: CodeAction,
+}
+
+fn line_range(line: u32, start: u32, end: u32) -> Range {
+    Range::new(Position::new(line, start), Position::new(line, end))
+}
+
+fn diagnostic(range: Range, message: impl Into<String>) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        source: Some("wit-legacy-syntax".to_owned()),
+        message: message.into(),
+        ..Default::default()
+    }
+}
+
+fn fix(
+    uri: &Url,
+    title: impl Into<String>,
+    diagnostic: Diagnostic,
+    edit: TextEdit,
+) -> LegacyFix {
+    let mut changes = HashMap::new();
+    changes.insert(uri.clone(), vec![edit]);
+
+    LegacyFix {
+        action: CodeAction {
+            title: title.into(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![diagnostic.clone()]),
+            edit: Some(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        diagnostic,
+    }
+}
+
+/// Scans `text` for obsolete "document"-based WIT syntax and produces a
+/// quick-fix for each occurrence: a missing `package` header, dotted
+/// `self.<document>.<name>` / `<pkg>.<document>.<name>` `use` paths, and URL
+/// string annotations on imports/exports.
+pub fn scan(uri: &Url, text: &str) -> Vec<LegacyFix> {
+    let mut fixes = Vec::new();
+
+    if !has_package_header(text) {
+        fixes.push(fix(
+            uri,
+            "Insert a `package` header",
+            diagnostic(
+                line_range(0, 0, 0),
+                "WIT files must start with a `package namespace:name;` header",
+            ),
+            TextEdit {
+                range: line_range(0, 0, 0),
+                new_text: "package namespace:name;\n\n".to_owned(),
+            },
+        ));
+    }
+
+    let use_path = lazy_regex!(r"(?m)^([ \t]*use\s+)(self|[a-zA-Z0-9_-]+)\.([a-zA-Z0-9_-]+)\.(\{[^}]*\}|[a-zA-Z0-9_-]+)");
+    for cap in use_path.captures_iter(text) {
+        let whole = cap.get(0).unwrap();
+        let scope = &cap[2];
+        let document = &cap[3];
+        let names = &cap[4];
+
+        let replacement = if scope == "self" {
+            format!("{}{document}.{names}", &cap[1])
+        } else {
+            format!("{}<namespace>:{scope}/{document}.{names}", &cap[1])
+        };
+
+        let (line, start, end) = line_span(text, whole.start(), whole.end());
+        fixes.push(fix(
+            uri,
+            "Rewrite `use` path to package-qualified syntax",
+            diagnostic(
+                line_range(line, start, end),
+                "`use` paths with dotted document segments were replaced by slash-separated, \
+                 package-qualified paths",
+            ),
+            TextEdit {
+                range: line_range(line, start, end),
+                new_text: replacement,
+            },
+        ));
+    }
+
+    let url_annotation = lazy_regex!(r#"(?m)^([ \t]*(?:import|export)\s+[^;\n]*?)\s*"[^"]*""#);
+    for cap in url_annotation.captures_iter(text) {
+        let whole = cap.get(0).unwrap();
+        let without_url = cap[1].to_owned();
+
+        let (line, start, end) = line_span(text, whole.start(), whole.end());
+        fixes.push(fix(
+            uri,
+            "Remove obsolete URL annotation",
+            diagnostic(
+                line_range(line, start, end),
+                "URL annotations on imports/exports were dropped from WIT syntax",
+            ),
+            TextEdit {
+                range: line_range(line, start, end),
+                new_text: without_url,
+            },
+        ));
+    }
+
+    fixes
+}
+
+fn has_package_header(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .next()
+        .is_some_and(|line| line.starts_with("package"))
+}
+
+/// Converts a byte offset span into a (0-indexed line, start column, end column).
+fn line_span(text: &str, start: usize, end: usize) -> (u32, u32, u32) {
+    let line = text[..start].matches('\n').count() as u32;
+    let line_start = text[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    (
+        line,
+        (start - line_start) as u32,
+        (end - line_start) as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri() -> Url {
+        Url::parse("file:///legacy.wit").unwrap()
+    }
+
+    fn new_text(fixes: &[LegacyFix], uri: &Url) -> Vec<String> {
+        fixes
+            .iter()
+            .map(|fix| {
+                fix.action.edit.as_ref().unwrap().changes.as_ref().unwrap()[uri][0]
+                    .new_text
+                    .clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_has_package_header_ignores_leading_comments_and_blank_lines() {
+        assert!(has_package_header("package foo:bar;\n"));
+        assert!(has_package_header("\n// a comment\n\npackage foo:bar;\n"));
+        assert!(!has_package_header("// package foo:bar;\ninterface i {}"));
+        assert!(!has_package_header("interface i {}\n"));
+    }
+
+    #[test]
+    fn test_scan_inserts_a_package_header_when_missing() {
+        let fixes = scan(&uri(), "interface i {}\n");
+        assert_eq!(
+            new_text(&fixes, &uri())[0],
+            "package namespace:name;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_scan_rewrites_self_scoped_use_path_without_a_namespace() {
+        let text = "package foo:bar;\n\nuse self.types.{a-type};\n";
+        let fixes = scan(&uri(), text);
+        assert_eq!(new_text(&fixes, &uri()), vec!["use types.{a-type}"]);
+    }
+
+    #[test]
+    fn test_scan_rewrites_scoped_use_path_with_a_placeholder_namespace() {
+        let text = "package foo:bar;\n\nuse other.types.{a-type};\n";
+        let fixes = scan(&uri(), text);
+        assert_eq!(
+            new_text(&fixes, &uri()),
+            vec!["use <namespace>:other/types.{a-type}"]
+        );
+    }
+
+    #[test]
+    fn test_scan_drops_url_annotations_on_imports_and_exports() {
+        let text = "package foo:bar;\n\nworld w {\n  import foo: func() \"https://example.com\";\n}\n";
+        let fixes = scan(&uri(), text);
+        assert_eq!(new_text(&fixes, &uri()), vec!["  import foo: func()"]);
+    }
+
+    #[test]
+    fn test_scan_on_already_current_syntax_produces_no_fixes() {
+        let text = "package foo:bar;\n\nuse other:pkg/types.{a-type};\n\nworld w {\n  import foo: func();\n}\n";
+        assert!(scan(&uri(), text).is_empty());
+    }
+
+    #[test]
+    fn test_line_span_reports_the_enclosing_lines_0_indexed_column_range() {
+        let text = "package foo:bar;\nuse self.types.{a};\n";
+        let start = text.find("use").unwrap();
+        let end = start + "use self.types.{a}".len();
+        assert_eq!(line_span(text, start, end), (1, 0, 18));
+    }
+}
@@ -0,0 +1,465 @@
+use lazy_regex::lazy_regex;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Position, Range, Url,
+};
+use std::{
+    process::Stdio, collections::{HashMap, HashSet}, path::Path,
+};
+
+use tokio::process::Command;
+
+pub(crate) mod legacy;
+pub(crate) mod parallel;
+pub(crate) mod sarif;
+pub(crate) mod watch;
+
+pub use watch::Watch;
+
+pub struct Linter {
+    cmd: Command,
+}
+
+impl Linter {
+    /// Builds a linter that scans `dir`, e.g. a package or workspace root.
+    pub fn for_directory(dir: &Path) -> Self {
+        let mut cmd = Command::new("wasm-tools");
+        cmd.arg("component");
+        cmd.arg("wit");
+        cmd.arg(dir);
+        cmd.stderr(Stdio::piped());
+        cmd.stdout(Stdio::piped());
+        // So a superseded in-flight run (see `watch`) actually stops the
+        // `wasm-tools` subprocess instead of leaving it running unobserved.
+        cmd.kill_on_drop(true);
+
+        Self { cmd }
+    }
+
+    pub async fn run(&mut self) -> std::io::Result<HashMap<Url, Vec<Diagnostic>>> {
+        let child = self.cmd.spawn()?;
+        let output = child.wait_with_output().await?;
+
+        if let Ok(stderr) = String::from_utf8(output.stderr) {
+            Ok(ouput_from_str(stderr))
+        } else {
+            Ok(HashMap::new())
+        }
+    }
+}
+
+
+/// One file's diagnostics, kept separate by the source that produced them
+/// so that updating one source doesn't require clobbering the other.
+#[derive(Default, Clone)]
+struct FileDiagnostics {
+    /// Syntax/name-resolution errors from the in-process `wit_parser`.
+    native: Vec<Diagnostic>,
+    /// Lint output from the `wasm-tools` subprocess.
+    check: Vec<Diagnostic>,
+}
+
+impl FileDiagnostics {
+    fn merged(&self) -> Vec<Diagnostic> {
+        self.native.iter().chain(&self.check).cloned().collect()
+    }
+}
+
+/// Diagnostics for every open file, partitioned by source (`native`'s
+/// in-process `wit_parser` errors vs. `check`'s `wasm-tools` lint output) so
+/// the two streams can coexist without one clearing the other, and tracked
+/// for which files' merged diagnostics changed since the last call to
+/// [`DiagnosticCollection::take_changed`] so the caller only has to publish
+/// for files whose diagnostics actually differ -- avoiding the flicker of
+/// clearing then repopulating every file on every lint pass.
+#[derive(Default)]
+pub struct DiagnosticCollection {
+    files: HashMap<Url, FileDiagnostics>,
+    changed: HashSet<Url>,
+}
+
+impl DiagnosticCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces `url`'s native (in-process `wit_parser`) diagnostics.
+    pub fn set_native(&mut self, url: Url, diagnostics: Vec<Diagnostic>) {
+        self.update(url, |entry| entry.native = diagnostics);
+    }
+
+    /// Appends one `wasm-tools` diagnostic to `url`'s check diagnostics.
+    pub fn add_check(&mut self, url: Url, diagnostic: Diagnostic) {
+        self.update(url, |entry| entry.check.push(diagnostic));
+    }
+
+    /// Clears every file's `wasm-tools` check diagnostics, e.g. before a
+    /// fresh lint run whose output will repopulate them via `add_check`.
+    pub fn clear_check(&mut self) {
+        let urls: Vec<Url> = self.files.keys().cloned().collect();
+        for url in urls {
+            self.update(url, |entry| entry.check.clear());
+        }
+    }
+
+    /// Applies `mutate` to `url`'s entry and marks it changed if doing so
+    /// altered its merged diagnostics.
+    fn update(&mut self, url: Url, mutate: impl FnOnce(&mut FileDiagnostics)) {
+        let entry = self.files.entry(url.clone()).or_default();
+        let before = entry.merged();
+        mutate(entry);
+        if entry.merged() != before {
+            self.changed.insert(url);
+        }
+    }
+
+    /// Returns the merged diagnostics for every `Url` whose merged set has
+    /// changed since the last call, and resets the changed-file tracking so
+    /// the next call only reports further changes.
+    pub fn take_changed(&mut self) -> HashMap<Url, Vec<Diagnostic>> {
+        self.changed
+            .drain()
+            .map(|url| {
+                let diagnostics = self.files.get(&url).map_or_else(Vec::new, FileDiagnostics::merged);
+                (url, diagnostics)
+            })
+            .collect()
+    }
+}
+
+/// Converts a rustc-style error message (`--> file:line:col` followed by a
+/// caret-underlined source snippet) into diagnostics for a single,
+/// already-known file. `wit_parser::Resolve`'s parse/resolution errors are
+/// rendered in this same style, so this is shared by both the `wasm-tools`
+/// subprocess output and in-process `wit_parser` errors.
+pub fn diagnostics_from_error(message: &str) -> Vec<Diagnostic> {
+    let regex = lazy_regex!(r":(.*)\s*-->\s*(?:.*):(\d+):(\d+)\s.*\s.*\s.*(\^\-*)"m);
+
+    regex
+        .captures_iter(message)
+        .map(|cap| {
+            let message = cap[1].to_string();
+            let line = cap[2].parse::<u32>().unwrap_or_default().saturating_sub(1);
+            let character = cap[3].parse::<u32>().unwrap_or_default().saturating_sub(1);
+            let marker = cap[4].to_string();
+
+            Diagnostic {
+                range: Range {
+                    start: Position { line, character },
+                    end: Position {
+                        line,
+                        character: character + marker.len() as u32,
+                    },
+                },
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("wit-parser".to_owned()),
+                message,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// One `error:`/`warning:` diagnostic as found in `wasm-tools component wit`
+/// output, before its file path has been resolved to a [`Url`]. `line`,
+/// `start_column`, `end_line`, and `end_column` are already 0-based and
+/// end-exclusive, i.e. ready to drop straight into an LSP [`Range`].
+struct RawDiagnostic {
+    file: String,
+    severity: DiagnosticSeverity,
+    message: String,
+    line: u32,
+    start_column: u32,
+    end_line: u32,
+    end_column: u32,
+    /// Trailing `= help: ...` / `= note: ...` lines, attached as
+    /// [`DiagnosticRelatedInformation`] pointing back at the primary range.
+    related: Vec<String>,
+}
+
+/// Matches a diagnostic's header line: `error: message` or
+/// `error[code]: message` (and the `warning` equivalents).
+fn parse_header(line: &str) -> Option<(DiagnosticSeverity, String)> {
+    let regex = lazy_regex!(r"^(error|warning)(?:\[[^\]]*\])?:\s*(.*)$");
+    let cap = regex.captures(line.trim_start())?;
+    let severity = if &cap[1] == "warning" {
+        DiagnosticSeverity::WARNING
+    } else {
+        DiagnosticSeverity::ERROR
+    };
+    Some((severity, cap[2].to_string()))
+}
+
+/// Matches the `--> file:line:col` location line.
+fn parse_location(line: &str) -> Option<(String, u32, u32)> {
+    let regex = lazy_regex!(r"^\s*-->\s*(.+):(\d+):(\d+)\s*$");
+    let cap = regex.captures(line)?;
+    Some((
+        cap[1].to_string(),
+        cap[2].parse().ok()?,
+        cap[3].parse().ok()?,
+    ))
+}
+
+/// Matches a source-snippet gutter line (`NNN | source text`), returning its
+/// 1-based line number.
+fn parse_gutter_line_number(line: &str) -> Option<u32> {
+    let regex = lazy_regex!(r"^\s*(\d+)\s*\|");
+    regex.captures(line)?[1].parse().ok()
+}
+
+/// Matches the caret-underline row below a snippet (`    | ^^^^-- message`,
+/// with no line number before the `|`), returning the 0-based start/end
+/// column of the `^`/`-` run, derived from its actual extent on the row
+/// rather than assumed to be a single contiguous block from the `-->`
+/// column.
+fn parse_underline(line: &str) -> Option<(u32, u32)> {
+    let regex = lazy_regex!(r"^\s*\|(.*)$");
+    // A gutter line also matches `\s*\|`, so only treat this as an
+    // underline when there's no line number before the pipe.
+    if parse_gutter_line_number(line).is_some() {
+        return None;
+    }
+    let content = &regex.captures(line)?[1];
+    // Drop the one space separating `|` from the snippet text, so that
+    // column 0 of `content` is column 0 of the source line above it.
+    let content = content.strip_prefix(' ').unwrap_or(content);
+    let start = content.find(['^', '-'])?;
+    let end = content.rfind(['^', '-'])? + 1;
+    Some((start as u32, end as u32))
+}
+
+/// Matches a trailing `= help: ...` / `= note: ...` line, returning
+/// `"help: ..."` / `"note: ..."`.
+fn parse_note(line: &str) -> Option<String> {
+    let regex = lazy_regex!(r"^\s*=\s*(help|note):\s*(.*)$");
+    let cap = regex.captures(line)?;
+    Some(format!("{}: {}", &cap[1], &cap[2]))
+}
+
+/// Parses every `error:`/`warning:` diagnostic out of `wasm-tools component
+/// wit` output: its severity, message, source span (which may cover
+/// multiple snippet lines), and any trailing `help`/`note` context.
+fn parse_raw_diagnostics(output: &str) -> Vec<RawDiagnostic> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut diagnostics = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some((severity, message)) = parse_header(lines[i]) else {
+            i += 1;
+            continue;
+        };
+        i += 1;
+
+        let mut location = None;
+        while i < lines.len() {
+            if let Some(loc) = parse_location(lines[i]) {
+                location = Some(loc);
+                i += 1;
+                break;
+            }
+            if parse_header(lines[i]).is_some() {
+                break;
+            }
+            i += 1;
+        }
+        let Some((file, line, start_column)) = location else {
+            continue;
+        };
+        // 0-based, matching the underline's own 0-based column indices.
+        let start_column = start_column.saturating_sub(1);
+
+        let mut last_gutter_line = line;
+        // Fall back to a single-character range if no underline row is
+        // found at all.
+        let mut end_column = start_column + 1;
+        let mut related = Vec::new();
+
+        while i < lines.len() {
+            if let Some(gutter_line) = parse_gutter_line_number(lines[i]) {
+                last_gutter_line = gutter_line;
+            } else if let Some((_, end)) = parse_underline(lines[i]) {
+                end_column = end;
+            } else if let Some(note) = parse_note(lines[i]) {
+                related.push(note);
+            } else if parse_header(lines[i]).is_some() {
+                break;
+            }
+            i += 1;
+        }
+
+        diagnostics.push(RawDiagnostic {
+            file,
+            severity,
+            message,
+            line: line.saturating_sub(1),
+            start_column,
+            end_line: last_gutter_line.saturating_sub(1),
+            end_column,
+            related,
+        });
+    }
+
+    diagnostics
+}
+
+fn ouput_from_str(s: String) -> HashMap<Url, Vec<Diagnostic>> {
+    let mut hashmap: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+    let cwd = std::env::current_dir().unwrap();
+
+    for raw in parse_raw_diagnostics(&s) {
+        let Ok(uri) = Url::from_file_path(cwd.join(&raw.file)) else {
+            continue;
+        };
+
+        let range = Range {
+            start: Position {
+                line: raw.line,
+                character: raw.start_column,
+            },
+            end: Position {
+                line: raw.end_line,
+                character: raw.end_column,
+            },
+        };
+
+        let related_information = (!raw.related.is_empty()).then(|| {
+            raw.related
+                .iter()
+                .map(|note| DiagnosticRelatedInformation {
+                    location: Location {
+                        uri: uri.clone(),
+                        range,
+                    },
+                    message: note.clone(),
+                })
+                .collect()
+        });
+
+        hashmap.entry(uri).or_default().push(Diagnostic {
+            range,
+            severity: Some(raw.severity),
+            message: raw.message,
+            related_information,
+            ..Default::default()
+        });
+    }
+
+    hashmap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(message: &str) -> Diagnostic {
+        Diagnostic {
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_take_changed_only_reports_files_that_actually_differ() {
+        let mut diagnostics = DiagnosticCollection::new();
+        let url = Url::parse("file:///foo.wit").unwrap();
+
+        diagnostics.set_native(url.clone(), vec![diagnostic("bad syntax")]);
+        assert_eq!(diagnostics.take_changed(), HashMap::from([(
+            url.clone(),
+            vec![diagnostic("bad syntax")],
+        )]));
+
+        // Re-applying the same diagnostics changes nothing, so it should not
+        // be reported as changed again.
+        diagnostics.set_native(url.clone(), vec![diagnostic("bad syntax")]);
+        assert!(diagnostics.take_changed().is_empty());
+    }
+
+    #[test]
+    fn test_native_and_check_diagnostics_coexist() {
+        let mut diagnostics = DiagnosticCollection::new();
+        let url = Url::parse("file:///foo.wit").unwrap();
+
+        diagnostics.set_native(url.clone(), vec![diagnostic("native error")]);
+        diagnostics.add_check(url.clone(), diagnostic("check warning"));
+
+        let changed = diagnostics.take_changed();
+        assert_eq!(
+            changed.get(&url).unwrap(),
+            &vec![diagnostic("native error"), diagnostic("check warning")]
+        );
+
+        // Clearing check diagnostics leaves the native ones in place.
+        diagnostics.clear_check();
+        let changed = diagnostics.take_changed();
+        assert_eq!(changed.get(&url).unwrap(), &vec![diagnostic("native error")]);
+    }
+
+    #[test]
+    fn test_parse_raw_diagnostics_reports_severity_span_and_notes() {
+        let output = "\
+error: failed to resolve type `foo`
+  --> pkg/world.wit:3:10
+   |
+ 3 | interface x {
+   |          ^^^ expected a valid type
+   |
+   = help: define `foo` before using it
+   = note: types must be declared before use
+
+warning: unused `use`
+  --> pkg/world.wit:7:5
+   |
+ 7 |     use bar;
+   |     ^^^^^^^^
+";
+
+        let diagnostics = parse_raw_diagnostics(output);
+        assert_eq!(diagnostics.len(), 2);
+
+        let error = &diagnostics[0];
+        assert_eq!(error.severity, DiagnosticSeverity::ERROR);
+        assert_eq!(error.message, "failed to resolve type `foo`");
+        assert_eq!(error.file, "pkg/world.wit");
+        assert_eq!((error.line, error.start_column), (2, 9));
+        assert_eq!((error.end_line, error.end_column), (2, 12));
+        assert_eq!(
+            error.related,
+            vec![
+                "help: define `foo` before using it".to_string(),
+                "note: types must be declared before use".to_string(),
+            ]
+        );
+
+        let warning = &diagnostics[1];
+        assert_eq!(warning.severity, DiagnosticSeverity::WARNING);
+        assert_eq!((warning.line, warning.start_column), (6, 4));
+        assert_eq!((warning.end_line, warning.end_column), (6, 12));
+        assert!(warning.related.is_empty());
+    }
+
+    #[test]
+    fn test_ouput_from_str_accumulates_diagnostics_per_file() {
+        let output = "\
+error: first problem
+  --> pkg/world.wit:1:1
+   |
+ 1 | package foo:bar
+   | ^ expected `;`
+
+error: second problem
+  --> pkg/world.wit:3:1
+   |
+ 3 | garbage
+   | ^^^^^^^ unexpected token
+";
+
+        let result = ouput_from_str(output.to_string());
+        assert_eq!(result.len(), 1);
+        let (_, diagnostics) = result.into_iter().next().unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message, "first problem");
+        assert_eq!(diagnostics[1].message, "second problem");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use dashmap::DashMap;
+use tokio::sync::{mpsc, Semaphore};
+use tower_lsp::lsp_types::{Diagnostic, Url};
+
+use super::Linter;
+
+/// Finds every WIT package directory under `root`: `root` itself if it has
+/// `*.wit` files directly inside it, plus any such directory nested under
+/// it. `deps/` trees are skipped, since they belong to whichever package
+/// pulled them in rather than being packages to lint on their own.
+fn discover_packages(root: &Path, packages: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return;
+    };
+
+    let mut has_wit_file = false;
+    let mut subdirs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path.file_name().and_then(|name| name.to_str()) != Some("deps") {
+                subdirs.push(path);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "wit") {
+            has_wit_file = true;
+        }
+    }
+
+    if has_wit_file {
+        packages.push(root.to_path_buf());
+    }
+    for subdir in subdirs {
+        discover_packages(&subdir, packages);
+    }
+}
+
+/// Lints every WIT package directory discovered under `roots`, running up
+/// to `parallelism` `wasm-tools component wit` invocations at once so a
+/// multi-package workspace isn't serialized behind its slowest directory.
+///
+/// `on_result` fires as soon as each directory's invocation completes (fed
+/// through a channel in the same spirit as a crossbeam channel, decoupling
+/// the finishing child processes from this loop), so a caller can publish
+/// diagnostics incrementally instead of waiting for every directory to
+/// finish. A directory that fails to lint (e.g. `wasm-tools` isn't
+/// installed, or exits with an I/O error) is skipped rather than aborting
+/// the rest of the workspace.
+///
+/// Returns every discovered directory's diagnostics merged into a shared
+/// map, keyed by file, so a caller that only wants the final combined
+/// result (e.g. to render one SARIF log) doesn't have to re-merge
+/// `on_result`'s callbacks itself.
+pub(crate) async fn lint_workspace(
+    roots: &[PathBuf],
+    parallelism: usize,
+    mut on_result: impl FnMut(&Url, &[Diagnostic]),
+) -> Arc<DashMap<Url, Vec<Diagnostic>>> {
+    let mut packages = Vec::new();
+    for root in roots {
+        discover_packages(root, &mut packages);
+    }
+
+    let merged = Arc::new(DashMap::new());
+    let semaphore = Arc::new(Semaphore::new(parallelism.max(1)));
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    for package in packages {
+        let semaphore = semaphore.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            // Held until the run completes, bounding how many `wasm-tools`
+            // children are alive at once.
+            let Ok(_permit) = semaphore.acquire().await else {
+                return;
+            };
+            let result = Linter::for_directory(&package).run().await;
+            let _ = tx.send(result);
+        });
+    }
+    // Drop our own sender so `rx.recv()` below returns `None` once every
+    // spawned task's sender has also been dropped.
+    drop(tx);
+
+    while let Some(result) = rx.recv().await {
+        let Ok(diagnostics) = result else { continue };
+        for (uri, diags) in diagnostics {
+            on_result(&uri, &diags);
+            merged.insert(uri, diags);
+        }
+    }
+
+    merged
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Url};
+
+/// Maps a [`DiagnosticSeverity`] to its SARIF 2.1.0 `level`. There's no
+/// SARIF equivalent of [`DiagnosticSeverity::HINT`], so it and anything
+/// unrecognized fold into `note`, the least severe real SARIF level.
+fn level(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        _ => "note",
+    }
+}
+
+/// Derives a stable `ruleId` from a diagnostic's message, since
+/// `wasm-tools component wit` doesn't emit rule codes itself: the message's
+/// lead clause (up to the first backtick or colon introducing the quoted
+/// name or detail), slugified to `kebab-case`.
+fn rule_id(message: &str) -> String {
+    let head = message
+        .split(['`', ':'])
+        .next()
+        .unwrap_or(message)
+        .trim()
+        .to_lowercase();
+
+    let slug: String = head
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+
+    if slug.is_empty() {
+        "wit-diagnostic".to_owned()
+    } else {
+        slug
+    }
+}
+
+/// Converts a 0-based, end-exclusive LSP [`Diagnostic::range`] into a
+/// SARIF `region`, whose line/column numbers are 1-based.
+fn region(diagnostic: &Diagnostic) -> Value {
+    json!({
+        "startLine": diagnostic.range.start.line + 1,
+        "startColumn": diagnostic.range.start.character + 1,
+        "endLine": diagnostic.range.end.line + 1,
+        "endColumn": diagnostic.range.end.character + 1,
+    })
+}
+
+fn result(uri: &Url, diagnostic: &Diagnostic) -> Value {
+    json!({
+        "ruleId": rule_id(&diagnostic.message),
+        "level": level(diagnostic.severity),
+        "message": { "text": diagnostic.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": uri.as_str() },
+                "region": region(diagnostic),
+            },
+        }],
+    })
+}
+
+/// Builds a SARIF 2.1.0 log containing a single `run`, from the diagnostics
+/// produced by [`super::Linter::run`]. Used by the CLI's one-shot SARIF
+/// export mode so `wasm-tools`-based WIT checks can run as a CI lint and
+/// upload their results to a code-scanning dashboard.
+pub(crate) fn build(diagnostics: &HashMap<Url, Vec<Diagnostic>>) -> Value {
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .flat_map(|(uri, diags)| diags.iter().map(move |diagnostic| result(uri, diagnostic)))
+        .collect();
+
+    let mut rule_ids: Vec<&str> = results
+        .iter()
+        .filter_map(|result| result["ruleId"].as_str())
+        .collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    let rules: Vec<Value> = rule_ids
+        .into_iter()
+        .map(|id| json!({ "id": id }))
+        .collect();
+
+    json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "wasm-tools",
+                    "informationUri": "https://github.com/bytecodealliance/wasm-tools",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn diagnostic(severity: DiagnosticSeverity, message: &str) -> Diagnostic {
+        Diagnostic {
+            range: Range::new(Position::new(2, 9), Position::new(2, 13)),
+            severity: Some(severity),
+            message: message.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rule_id_slugifies_the_messages_lead_clause() {
+        assert_eq!(rule_id("failed to resolve type `foo`"), "failed-to-resolve-type");
+        assert_eq!(rule_id("unused `use`"), "unused");
+        assert_eq!(rule_id(""), "wit-diagnostic");
+    }
+
+    #[test]
+    fn test_build_maps_severity_region_and_rule_id() {
+        let url = Url::parse("file:///pkg/world.wit").unwrap();
+        let diagnostics = HashMap::from([(
+            url.clone(),
+            vec![diagnostic(DiagnosticSeverity::ERROR, "failed to resolve type `foo`")],
+        )]);
+
+        let log = build(&diagnostics);
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "failed-to-resolve-type");
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["message"]["text"], "failed to resolve type `foo`");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            url.as_str()
+        );
+        assert_eq!(result["locations"][0]["physicalLocation"]["region"]["startLine"], 3);
+        assert_eq!(result["locations"][0]["physicalLocation"]["region"]["startColumn"], 10);
+
+        let rules = log["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["id"], "failed-to-resolve-type");
+    }
+}
@@ -0,0 +1,123 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use tokio::sync::{mpsc, Mutex};
+use tower_lsp::Client;
+
+use super::{DiagnosticCollection, Linter};
+
+/// Rapid `didChange`/`didSave` events within this window of each other
+/// coalesce into a single `wasm-tools` run, the same way `cargo-watch`
+/// debounces filesystem events before re-running `cargo check`.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// A long-lived watch loop, one per workspace root, that re-runs
+/// `wasm-tools` whenever [`Watch::trigger`] is called. Bursts of triggers
+/// within [`DEBOUNCE`] of each other collapse into a single run, and a
+/// trigger that arrives while a run is still in flight supersedes it: the
+/// `wasm-tools` child is killed (see `Linter::for_directory`'s
+/// `kill_on_drop`) and a fresh debounce window starts immediately.
+///
+/// Resulting diagnostics are streamed into the shared [`DiagnosticCollection`]
+/// as `check` diagnostics and published for whichever files actually
+/// changed, so files that start compiling cleanly have their stale errors
+/// cleared rather than left stuck until the next full republish.
+pub struct Watch {
+    trigger: mpsc::UnboundedSender<()>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Watch {
+    /// Starts watching `root`, publishing through `client` as re-lints
+    /// complete and merging results into `diagnostics`.
+    pub fn start(root: PathBuf, client: Client, diagnostics: Arc<Mutex<DiagnosticCollection>>) -> Self {
+        let (trigger, rx) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(Self::watch_loop(root, client, diagnostics, rx));
+
+        Self { trigger, task }
+    }
+
+    /// Queues a re-lint of this watch's root, debounced against other
+    /// recent calls.
+    pub fn trigger(&self) {
+        // The receiver only disappears once `task` has exited, which only
+        // happens after `stop` drops `trigger` itself; an error here would
+        // mean `self` no longer owns a live loop, which can't happen.
+        let _ = self.trigger.send(());
+    }
+
+    /// Stops the watch loop, killing any in-flight `wasm-tools` run.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+
+    async fn watch_loop(
+        root: PathBuf,
+        client: Client,
+        diagnostics: Arc<Mutex<DiagnosticCollection>>,
+        mut rx: mpsc::UnboundedReceiver<()>,
+    ) {
+        let mut linter = Linter::for_directory(&root);
+        // Set when a run was just superseded by a newer trigger, so the
+        // next iteration re-enters debouncing immediately instead of
+        // blocking for yet another trigger beyond the one that superseded.
+        let mut primed = false;
+
+        loop {
+            if !primed {
+                match rx.recv().await {
+                    Some(()) => {}
+                    None => return,
+                }
+            }
+            primed = false;
+
+            // Coalesce anything else that arrives within the debounce
+            // window into the run it's about to trigger.
+            loop {
+                tokio::select! {
+                    () = tokio::time::sleep(DEBOUNCE) => break,
+                    next = rx.recv() => match next {
+                        Some(()) => continue,
+                        None => return,
+                    },
+                }
+            }
+
+            tokio::select! {
+                // A newer trigger supersedes this run: dropping `linter.run()`
+                // here kills the in-flight `wasm-tools` child.
+                next = rx.recv() => match next {
+                    Some(()) => {
+                        primed = true;
+                        continue;
+                    }
+                    None => return,
+                },
+                result = linter.run() => {
+                    let Ok(output) = result else { continue };
+
+                    let mut collection = diagnostics.lock().await;
+                    collection.clear_check();
+                    for (uri, diags) in output {
+                        for diagnostic in diags {
+                            collection.add_check(uri, diagnostic);
+                        }
+                    }
+                    let changed = collection.take_changed();
+                    drop(collection);
+
+                    for (uri, diags) in changed {
+                        client.publish_diagnostics(uri, diags, None).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -1,37 +1,92 @@
 use std::{
+    collections::HashMap,
     fmt::Display,
-    path::Path
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
+use tokio::sync::Mutex;
+
 use tower_lsp::{
     lsp_types::{
-        DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-        DidSaveTextDocumentParams, Hover, HoverParams, InitializeParams,
-        InitializeResult, InitializedParams, MessageType,
-        SemanticTokens, SemanticTokensParams, SemanticTokensResult, ServerInfo, Url,
-        WillSaveTextDocumentParams,
+        CodeAction, CodeActionKind, Command, Diagnostic, DidChangeTextDocumentParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+        CodeActionOrCommand, CodeActionParams, CodeActionResponse, CompletionParams,
+        CompletionResponse, DidSaveTextDocumentParams, DocumentSymbolParams,
+        DocumentSymbolResponse, ExecuteCommandParams, GotoDefinitionParams,
+        GotoDefinitionResponse, Hover, HoverParams, InitializeParams, InitializeResult,
+        InitializedParams, Location, MessageType, ReferenceParams, SemanticTokens,
+        SemanticTokensDelta, SemanticTokensDeltaParams, SemanticTokensFullDeltaResult,
+        SemanticTokensParams, SemanticTokensRangeParams, SemanticTokensRangeResult,
+        SemanticTokensResult, ServerInfo, SymbolInformation, Url, WillSaveTextDocumentParams,
+        WorkspaceSymbolParams,
     },
     Client,
 };
+use wit_parser::Resolve;
 
+mod bindgen;
 mod capabilities;
 mod linter;
+mod registry;
 mod wit;
+mod workspace;
+
+use linter::{legacy, DiagnosticCollection, Linter, Watch};
+use registry::Registry;
+use wit::{flatten_semantic_tokens, File};
+use workspace::Workspace;
 
-use linter::Linter;
-use wit::File;
+/// The `workspace/executeCommand` command that generates bindings for the
+/// active document's world. Arguments are `[uri: string, target: string]`.
+const GENERATE_BINDINGS_COMMAND: &str = "wit.generateBindings";
+
+/// The `workspace/executeCommand` command that fetches a `use`d package
+/// that's missing from `deps/` from [`workspace::REGISTRY_URL_ENV`].
+/// Arguments are `[uri: string, package: string]`. Unlike the automatic
+/// fallback in [`Workspace::load`], this runs whenever invoked -- from the
+/// "download missing package" code action `code_action` offers, or
+/// directly -- rather than only as a retry after a failed resolution.
+const FETCH_DEPENDENCIES_COMMAND: &str = "wit.fetchDependencies";
 
 pub struct Handler {
     client: Client,
+    workspaces: Mutex<HashMap<PathBuf, Arc<Workspace>>>,
+    /// The last semantic tokens result published per file, keyed by the
+    /// file's `Url` and stored with the `result_id` the client will echo
+    /// back in `textDocument/semanticTokens/full/delta`.
+    token_cache: Mutex<HashMap<Url, (String, Vec<u32>)>>,
+    /// Native (`wit_parser`) and check (`wasm-tools`) diagnostics per file,
+    /// so `lint` only has to `publishDiagnostics` for files whose merged
+    /// diagnostics actually changed. Shared with the spawned [`Watch`] loops,
+    /// which populate `check` diagnostics asynchronously.
+    diagnostics: Arc<Mutex<DiagnosticCollection>>,
+    /// One debounced `wasm-tools` watch loop per package directory that's
+    /// been linted so far, started lazily the first time a file inside it is
+    /// linted without native diagnostics.
+    watches: Mutex<HashMap<PathBuf, Arc<Watch>>>,
 }
 
 impl Handler {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            workspaces: Mutex::new(HashMap::new()),
+            token_cache: Mutex::new(HashMap::new()),
+            diagnostics: Arc::new(Mutex::new(DiagnosticCollection::new())),
+            watches: Mutex::new(HashMap::new()),
+        }
     }
 
     pub async fn initialize(&self, params: &InitializeParams) -> InitializeResult {
-        let _ = params;
+        if let Some(root) = params
+            .root_uri
+            .as_ref()
+            .and_then(|uri| uri.to_file_path().ok())
+        {
+            self.load_workspace(root).await;
+        }
+
         InitializeResult {
             capabilities: capabilities::server_capabilities(),
             server_info: Some(ServerInfo {
@@ -41,6 +96,65 @@ impl Handler {
         }
     }
 
+    /// Resolves the workspace rooted at `root`, reusing the cached
+    /// resolution if one already exists and still matches what's on disk.
+    /// Logs (and re-writes `wkg.lock`) when a dependency under `deps/` has
+    /// changed since it was resolved.
+    async fn load_workspace(&self, root: PathBuf) -> Option<Arc<Workspace>> {
+        let cached = self.workspaces.lock().await.get(&root).cloned();
+        if let Some(workspace) = cached {
+            if !workspace.is_stale().unwrap_or(false) {
+                return Some(workspace);
+            }
+        }
+
+        let workspace = Workspace::load(&root).await.ok()?;
+
+        if let Ok(stale) = workspace.stale_dependencies().await {
+            if !stale.is_empty() {
+                self.log(format!(
+                    "Workspace dependencies changed on disk: {}",
+                    stale.join(", ")
+                ))
+                .await;
+            }
+        }
+        let _ = workspace.write_lockfile().await;
+
+        let workspace = Arc::new(workspace);
+        self.workspaces
+            .lock()
+            .await
+            .insert(root, workspace.clone());
+        Some(workspace)
+    }
+
+    /// Finds the cached [`Workspace`] that already covers `path`, or loads
+    /// one rooted at `path`'s parent directory if none does. This is what
+    /// lets `read_file`'s callers -- hover, goto-definition, references,
+    /// completion, and diagnostics -- query the shared, multi-package
+    /// `Resolve` instead of each re-parsing `path` on its own.
+    async fn workspace_for(&self, path: &Path) -> Option<Arc<Workspace>> {
+        let cached = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces.values().find(|w| w.contains_file(path)).cloned()
+        };
+        if let Some(workspace) = cached {
+            if !workspace.is_stale().unwrap_or(false) {
+                return Some(workspace);
+            }
+            // Reload the workspace's own root, not `path`'s parent -- for a
+            // file under `<root>/deps/<pkg>/`, `path.parent()` would be
+            // `deps/<pkg>` itself, which `load_workspace` would resolve as
+            // a brand-new, bogus one-package workspace instead of this
+            // file's real multi-package root.
+            return self.load_workspace(workspace.root().to_path_buf()).await;
+        }
+
+        let root = path.parent()?.to_path_buf();
+        self.load_workspace(root).await
+    }
+
     pub async fn initialized(&self, params: InitializedParams) {
         let _ = params;
         self.client
@@ -115,9 +229,205 @@ impl Handler {
         }
     }
 
+    pub async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Option<GotoDefinitionResponse> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let wit = self.read_file(uri.clone()).await.ok()?;
+        let (path, range) = wit.definition_at(position).ok()??;
+
+        let location_uri = Url::from_file_path(&path).unwrap_or(uri);
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            location_uri,
+            range,
+        )))
+    }
+
+    pub async fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let include_declaration = params.context.include_declaration;
+
+        let wit = self.read_file(uri.clone()).await.ok()?;
+        let references = wit.references_at(position, include_declaration).ok()?;
+
+        Some(
+            references
+                .into_iter()
+                .map(|(path, range)| {
+                    let uri = Url::from_file_path(&path).unwrap_or_else(|_| uri.clone());
+                    Location::new(uri, range)
+                })
+                .collect(),
+        )
+    }
+
+    pub async fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let wit = self.read_file(uri).await.ok()?;
+        let items = wit.completions_at(position).ok()?;
+
+        Some(CompletionResponse::Array(items))
+    }
+
+    pub async fn code_action(&self, params: CodeActionParams) -> Option<CodeActionResponse> {
+        let uri = params.text_document.uri;
+        let path = Path::new(uri.path());
+        let text = tokio::fs::read_to_string(path).await.ok()?;
+
+        let mut actions: CodeActionResponse = legacy::scan(&uri, &text)
+            .into_iter()
+            .map(|fix| CodeActionOrCommand::CodeAction(fix.action))
+            .collect();
+
+        if let Some(action) = fetch_dependency_action(&uri, path, &text) {
+            actions.push(action);
+        }
+
+        Some(actions)
+    }
+
+    /// Handles `workspace/executeCommand`: [`GENERATE_BINDINGS_COMMAND`]
+    /// re-parses the document named by its first argument, resolves its
+    /// first `world`, and hands it to the generator backend for the
+    /// requested target, surfacing parse failures as diagnostics the same
+    /// way `lint` does. [`FETCH_DEPENDENCIES_COMMAND`] fetches a missing
+    /// `use`d package into `deps/` and reloads the workspace so downstream
+    /// hover/definition pick it up.
+    pub async fn execute_command(&self, params: ExecuteCommandParams) -> Option<serde_json::Value> {
+        match params.command.as_str() {
+            GENERATE_BINDINGS_COMMAND => self.generate_bindings(params.arguments).await,
+            FETCH_DEPENDENCIES_COMMAND => self.fetch_dependencies(params.arguments).await,
+            _ => None,
+        }
+    }
+
+    async fn generate_bindings(&self, arguments: Vec<serde_json::Value>) -> Option<serde_json::Value> {
+        let mut args = arguments.into_iter();
+        let uri: Url = serde_json::from_value(args.next()?).ok()?;
+        let target: bindgen::Target = serde_json::from_value::<String>(args.next()?)
+            .ok()?
+            .parse()
+            .ok()?;
+
+        let path = Path::new(uri.path());
+        let text = tokio::fs::read_to_string(path).await.ok()?;
+
+        let mut resolve = Resolve::default();
+        let package = match resolve.push_str(path, &text) {
+            Ok(package) => package,
+            Err(err) => {
+                self.client
+                    .publish_diagnostics(
+                        uri,
+                        linter::diagnostics_from_error(&err.to_string()),
+                        None,
+                    )
+                    .await;
+                return None;
+            }
+        };
+
+        let Some((_, &world)) = resolve.packages[package].worlds.iter().next() else {
+            self.log("No `world` declaration found to generate bindings for").await;
+            return None;
+        };
+
+        match bindgen::generate(&mut resolve, world, target) {
+            Ok(source) => Some(serde_json::Value::String(source)),
+            Err(err) => {
+                self.log(format!("Binding generation failed: {err}")).await;
+                self.client
+                    .publish_diagnostics(
+                        uri,
+                        linter::diagnostics_from_error(&err.to_string()),
+                        None,
+                    )
+                    .await;
+                None
+            }
+        }
+    }
+
+    /// Fetches `arguments[1]` (a `namespace:name[@version]` package) from
+    /// [`workspace::REGISTRY_URL_ENV`] into the `deps/` directory beside
+    /// `arguments[0]`'s document, then drops the workspace rooted at that
+    /// document's directory from the cache, reloads it, and re-lints the
+    /// triggering document so the newly-fetched package is picked up
+    /// immediately.
+    async fn fetch_dependencies(&self, arguments: Vec<serde_json::Value>) -> Option<serde_json::Value> {
+        let mut args = arguments.into_iter();
+        let uri: Url = serde_json::from_value(args.next()?).ok()?;
+        let package: String = serde_json::from_value(args.next()?).ok()?;
+
+        let Ok(registry_url) = std::env::var(workspace::REGISTRY_URL_ENV) else {
+            self.log(format!(
+                "Set {} to fetch missing packages",
+                workspace::REGISTRY_URL_ENV
+            ))
+            .await;
+            return None;
+        };
+
+        let path = Path::new(uri.path());
+        let root = path.parent()?.to_path_buf();
+        let deps_dir = root.join("deps");
+
+        if let Err(err) = Registry::new(registry_url).fetch_into(&deps_dir, &package).await {
+            self.log(format!("Failed to fetch `{package}`: {err:#}")).await;
+            return None;
+        }
+
+        self.workspaces.lock().await.remove(&root);
+        let workspace = self.load_workspace(root).await;
+        self.lint(uri).await;
+
+        Some(serde_json::Value::Bool(workspace.is_some()))
+    }
+
+    pub async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Option<DocumentSymbolResponse> {
+        let wit = self.read_file(params.text_document.uri).await.ok()?;
+        let symbols = wit.document_symbols().ok()?;
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    pub async fn symbol(&self, params: WorkspaceSymbolParams) -> Option<Vec<SymbolInformation>> {
+        let query = params.query.to_lowercase();
+        let roots: Vec<PathBuf> = self.workspaces.lock().await.keys().cloned().collect();
+
+        let mut results = Vec::new();
+        for root in roots {
+            for path in workspace::wit_files_in(&root) {
+                let Ok(text) = tokio::fs::read_to_string(&path).await else {
+                    continue;
+                };
+                let Ok(uri) = Url::from_file_path(&path) else {
+                    continue;
+                };
+                let Ok(symbols) = File::new(text).document_symbols() else {
+                    continue;
+                };
+                collect_matching_symbols(&symbols, &uri, &query, None, &mut results);
+            }
+        }
+
+        Some(results)
+    }
+
     pub async fn semantic_tokens_full(&self, params: SemanticTokensParams) -> SemanticTokensResult {
-        if let Ok(wit) = self.read_file(params.text_document.uri).await {
-            return SemanticTokensResult::Tokens(wit.semantic_tokens());
+        let uri = params.text_document.uri;
+        if let Ok(wit) = self.read_file(uri.clone()).await {
+            let tokens = wit.semantic_tokens();
+            self.cache_tokens(uri, &tokens).await;
+            return SemanticTokensResult::Tokens(tokens);
         }
 
         SemanticTokensResult::Tokens(SemanticTokens {
@@ -126,21 +436,117 @@ impl Handler {
         })
     }
 
-    async fn lint(&self, url: Url) {
-        self.client
-            .publish_diagnostics(url.clone(), Vec::new(), None)
-            .await;
-        let path = Path::new(url.path());
+    /// Implements `textDocument/semanticTokens/full/delta`: re-tokenizes the
+    /// document and, if the client's `previous_result_id` still matches what
+    /// we last handed out for this file, returns an edit diff against the
+    /// cached token stream instead of the full array.
+    pub async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> SemanticTokensFullDeltaResult {
+        let uri = params.text_document.uri;
+        let Ok(wit) = self.read_file(uri.clone()).await else {
+            return SemanticTokensFullDeltaResult::Tokens(SemanticTokens {
+                result_id: None,
+                data: Vec::new(),
+            });
+        };
 
-        let mut linter = Linter::new(path);
+        let tokens = wit.semantic_tokens();
+        let current = flatten_semantic_tokens(&tokens);
+
+        let cache = self.token_cache.lock().await;
+        let previous = cache
+            .get(&uri)
+            .filter(|(id, _)| *id == params.previous_result_id)
+            .map(|(_, data)| data.clone());
+        drop(cache);
+
+        let result_id = tokens.result_id.clone();
+        if let Some(previous) = previous {
+            let edits = wit::diff_semantic_tokens(&previous, &current);
+            self.token_cache
+                .lock()
+                .await
+                .insert(uri, (result_id.clone().unwrap_or_default(), current));
+            return SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+                result_id,
+                edits,
+            });
+        }
 
-        let Ok(output) = linter.run().await else {
+        self.cache_tokens(uri, &tokens).await;
+        SemanticTokensFullDeltaResult::Tokens(tokens)
+    }
+
+    async fn cache_tokens(&self, uri: Url, tokens: &SemanticTokens) {
+        let Some(result_id) = tokens.result_id.clone() else {
             return;
         };
+        let data = flatten_semantic_tokens(tokens);
+        self.token_cache.lock().await.insert(uri, (result_id, data));
+    }
+
+    pub async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> SemanticTokensRangeResult {
+        if let Ok(wit) = self.read_file(params.text_document.uri).await {
+            return SemanticTokensRangeResult::Tokens(wit.semantic_tokens_range(params.range));
+        }
+
+        SemanticTokensRangeResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: Vec::new(),
+        })
+    }
+
+    /// Re-lints `url` and publishes only the files whose diagnostics
+    /// actually changed. Native (in-process `wit_parser`) diagnostics for
+    /// `url` are computed synchronously and published immediately; the
+    /// slower `wasm-tools` check is skipped while they're present and
+    /// otherwise handed off to this package's debounced [`Watch`] loop,
+    /// which publishes its own results once it completes.
+    async fn lint(&self, url: Url) {
+        let path = Path::new(url.path());
+
+        let native = match self.read_file(url.clone()).await {
+            Ok(wit) => wit.diagnostics(),
+            Err(_) => Vec::new(),
+        };
+        let native_is_clean = native.is_empty();
+
+        let mut diagnostics = self.diagnostics.lock().await;
+        diagnostics.set_native(url, native);
+        let changed = diagnostics.take_changed();
+        drop(diagnostics);
+
+        for (uri, diags) in changed {
+            self.client.publish_diagnostics(uri, diags, None).await;
+        }
+
+        if native_is_clean {
+            let dir = path.parent().unwrap_or(path).to_path_buf();
+            self.watch_for(dir).await.trigger();
+        }
+    }
 
-        for (uri, diag) in output {
-            self.client.publish_diagnostics(uri, diag, None).await;
+    /// Returns this package directory's debounced `wasm-tools` watch loop,
+    /// starting one and registering it in `self.watches` the first time it's
+    /// requested.
+    async fn watch_for(&self, dir: PathBuf) -> Arc<Watch> {
+        let mut watches = self.watches.lock().await;
+        if let Some(watch) = watches.get(&dir) {
+            return watch.clone();
         }
+
+        let watch = Arc::new(Watch::start(
+            dir.clone(),
+            self.client.clone(),
+            self.diagnostics.clone(),
+        ));
+        watches.insert(dir, watch.clone());
+        watch
     }
 
     pub async fn shutdown(&self) {
@@ -154,10 +560,87 @@ impl Handler {
     pub async fn read_file(&self, uri: Url) -> std::io::Result<File> {
         let path = Path::new(uri.path());
         let text = tokio::fs::read_to_string(path).await?;
-        Ok(File::new(text))
+        let workspace = self.workspace_for(path).await;
+        Ok(File::new(text).with_context(path.to_path_buf(), workspace))
     }
 }
 
+/// Runs `wasm-tools component wit` against each of `dirs` and renders the
+/// combined diagnostics as a SARIF 2.1.0 log, for the CLI's one-shot
+/// `sarif` subcommand. Unlike [`Handler::lint`], this reports every
+/// directory's `wasm-tools` output directly, since there's no editor-side
+/// native `wit_parser` pass to defer to here.
+pub async fn emit_sarif(dirs: &[PathBuf]) -> std::io::Result<serde_json::Value> {
+    let mut diagnostics: HashMap<Url, Vec<_>> = HashMap::new();
+    for dir in dirs {
+        for (uri, diags) in Linter::for_directory(dir).run().await? {
+            diagnostics.entry(uri).or_default().extend(diags);
+        }
+    }
+    Ok(linter::sarif::build(&diagnostics))
+}
 
+/// Discovers every WIT package directory under `roots` and lints them in
+/// parallel, bounded by `parallelism`, rendering the combined diagnostics
+/// as a SARIF 2.1.0 log. Unlike [`emit_sarif`], a directory that fails to
+/// lint doesn't abort the rest of the workspace -- it's simply absent from
+/// the results, the same as a directory with no diagnostics.
+pub async fn emit_sarif_workspace(roots: &[PathBuf], parallelism: usize) -> serde_json::Value {
+    let merged = linter::parallel::lint_workspace(roots, parallelism, |_, _| {}).await;
+    let diagnostics: HashMap<Url, Vec<Diagnostic>> = merged
+        .iter()
+        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .collect();
+    linter::sarif::build(&diagnostics)
+}
 
+/// If `text` fails to resolve because of a `use` on a package that isn't
+/// present under `deps/`, builds a "download missing package" quick fix
+/// that runs [`FETCH_DEPENDENCIES_COMMAND`] for that package name.
+fn fetch_dependency_action(uri: &Url, path: &Path, text: &str) -> Option<CodeActionOrCommand> {
+    let mut resolve = Resolve::default();
+    let err = resolve.push_str(path, text).err()?;
+    let package = registry::missing_package_from_error(&format!("{err:#}"))?;
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!("Download missing package `{package}`"),
+        kind: Some(CodeActionKind::QUICKFIX),
+        command: Some(Command {
+            title: format!("Fetch `{package}`"),
+            command: FETCH_DEPENDENCIES_COMMAND.to_owned(),
+            arguments: Some(vec![
+                serde_json::to_value(uri).ok()?,
+                serde_json::to_value(package).ok()?,
+            ]),
+        }),
+        ..Default::default()
+    }))
+}
+
+/// Flattens a [`DocumentSymbol`] tree into [`SymbolInformation`] entries
+/// whose name contains `query` (case-insensitive), for `workspace/symbol`.
+#[allow(deprecated)]
+fn collect_matching_symbols(
+    symbols: &[tower_lsp::lsp_types::DocumentSymbol],
+    uri: &Url,
+    query: &str,
+    container_name: Option<&str>,
+    out: &mut Vec<SymbolInformation>,
+) {
+    for symbol in symbols {
+        if query.is_empty() || symbol.name.to_lowercase().contains(query) {
+            out.push(SymbolInformation {
+                name: symbol.name.clone(),
+                kind: symbol.kind,
+                tags: symbol.tags.clone(),
+                deprecated: None,
+                location: Location::new(uri.clone(), symbol.range),
+                container_name: container_name.map(str::to_owned),
+            });
+        }
 
+        if let Some(children) = &symbol.children {
+            collect_matching_symbols(children, uri, query, Some(&symbol.name), out);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+/// A minimal client for the component registry protocol (e.g. a `warg`
+/// instance, or anything speaking the `wasm-pkg` HTTP API), used to fetch a
+/// `use`d dependency that isn't present under `deps/` yet.
+pub struct Registry {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl Registry {
+    /// `base_url` should point at a registry root, e.g.
+    /// `https://registry.example.com`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetches the WIT source for `namespace:name[@version]` and returns its
+    /// raw bytes. Most registries serve a package as a component; callers
+    /// that need plain WIT text should extract it with `wit-component`
+    /// rather than assume this is already text.
+    pub async fn fetch(&self, package: &str) -> Result<Vec<u8>> {
+        let (name, version) = split_name_version(package);
+        let mut url = format!("{}/v1/packages/{name}", self.base_url);
+        if let Some(version) = version {
+            url.push_str(&format!("/{version}"));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch package `{package}` from registry"))?;
+
+        if !response.status().is_success() {
+            bail!(
+                "registry returned {} for package `{package}`",
+                response.status()
+            );
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Fetches `package` and writes it under `deps_dir/<name>/package.wasm`,
+    /// mirroring the layout `wit_parser::Resolve::push_dir` expects for a
+    /// `deps/` directory.
+    pub async fn fetch_into(&self, deps_dir: &Path, package: &str) -> Result<()> {
+        let bytes = self.fetch(package).await?;
+        let (name, _version) = split_name_version(package);
+        let dir = deps_dir.join(sanitize_package_name(name));
+        tokio::fs::create_dir_all(&dir).await?;
+        tokio::fs::write(dir.join("package.wasm"), bytes).await?;
+        Ok(())
+    }
+}
+
+/// Splits `namespace:name[@version]` into its name and, if present, version.
+fn split_name_version(package: &str) -> (&str, Option<&str>) {
+    package.split_once('@').map_or((package, None), |(n, v)| (n, Some(v)))
+}
+
+/// Turns a `namespace:name` package name into a filesystem-safe directory
+/// name for `deps/`, e.g. `wasi:clocks` -> `wasi_clocks`.
+fn sanitize_package_name(name: &str) -> String {
+    name.replace([':', '/'], "_")
+}
+
+/// Extracts the package name wit_parser reports as missing from a resolution
+/// error, e.g. `"package not found: wasi:clocks"` -> `Some("wasi:clocks")`.
+pub fn missing_package_from_error(message: &str) -> Option<&str> {
+    message
+        .split("package not found: ")
+        .nth(1)
+        .map(|rest| rest.lines().next().unwrap_or(rest).trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_name_version_separates_an_at_suffixed_version() {
+        assert_eq!(split_name_version("wasi:clocks"), ("wasi:clocks", None));
+        assert_eq!(
+            split_name_version("wasi:clocks@0.2.0"),
+            ("wasi:clocks", Some("0.2.0"))
+        );
+    }
+
+    #[test]
+    fn test_sanitize_package_name_replaces_colons_and_slashes() {
+        assert_eq!(sanitize_package_name("wasi:clocks"), "wasi_clocks");
+        assert_eq!(
+            sanitize_package_name("wasi:clocks/monotonic-clock"),
+            "wasi_clocks_monotonic-clock"
+        );
+    }
+
+    #[test]
+    fn test_missing_package_from_error_extracts_the_package_name() {
+        assert_eq!(
+            missing_package_from_error("package not found: wasi:clocks"),
+            Some("wasi:clocks")
+        );
+    }
+
+    #[test]
+    fn test_missing_package_from_error_stops_at_the_end_of_the_line() {
+        let message = "failed to resolve WIT package at .\n\nCaused by:\n    0: package not found: wasi:clocks@0.2.0\n    1: some other context line";
+        assert_eq!(
+            missing_package_from_error(message),
+            Some("wasi:clocks@0.2.0")
+        );
+    }
+
+    #[test]
+    fn test_missing_package_from_error_trims_surrounding_whitespace() {
+        assert_eq!(
+            missing_package_from_error("package not found:    wasi:clocks   \n"),
+            Some("wasi:clocks")
+        );
+    }
+
+    #[test]
+    fn test_missing_package_from_error_returns_none_for_unrelated_errors() {
+        assert_eq!(missing_package_from_error("some other failure"), None);
+        assert_eq!(missing_package_from_error(""), None);
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, Documentation, MarkupContent, MarkupKind, Position,
+};
+use wit_parser::ast::lex::{Token, Tokenizer};
+
+use super::super::workspace::Workspace;
+use super::definition::scan;
+use super::docs;
+
+/// WIT keywords offered regardless of cursor context.
+const KEYWORDS: &[&str] = &[
+    "world", "interface", "record", "variant", "enum", "flags", "resource", "func", "use",
+    "import", "export", "type",
+];
+
+/// The component-model built-in types, always valid wherever a type is expected.
+const BUILTIN_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "s8", "s16", "s32", "s64", "float32", "float64", "char", "string",
+    "bool", "list", "option", "result", "tuple",
+];
+
+/// What the cursor is positioned over, so [`super::File::completions_at`]
+/// can narrow its suggestions instead of always offering the full union of
+/// keywords, builtin types, and declared names.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Context {
+    /// Inside a `use ...;` clause: only interface names (local or, via the
+    /// workspace's `use` graph, from other packages) make sense here.
+    UsePath,
+    /// A type is expected: after a `:` field/param type, inside `<...>`, or
+    /// inside a `list<>`/`option<>`/... argument position. Keywords that
+    /// introduce a new top-level declaration don't belong here.
+    Type,
+    /// Anywhere else, e.g. at the top level of a file or the start of a
+    /// declaration's body.
+    Any,
+}
+
+/// Classifies the cursor's context by scanning every token up to `offset`
+/// and tracking whether it's inside an open `use` clause or immediately
+/// follows a token that only a type can follow.
+fn context_at(text: &str, offset: u32) -> Result<Context> {
+    let mut lexer = Tokenizer::new(text, 0)?;
+    let mut in_use = false;
+    let mut last_significant: Option<Token> = None;
+
+    while let Some((span, token)) = lexer.next()? {
+        if span.start >= offset {
+            break;
+        }
+        match token {
+            Token::Whitespace | Token::Comment => continue,
+            Token::Use => in_use = true,
+            Token::Semicolon => in_use = false,
+            _ => {}
+        }
+        last_significant = Some(token);
+    }
+
+    if in_use {
+        return Ok(Context::UsePath);
+    }
+
+    if matches!(
+        last_significant,
+        Some(Token::Colon | Token::LessThan | Token::Comma | Token::LeftParen | Token::RArrow)
+    ) {
+        return Ok(Context::Type);
+    }
+
+    Ok(Context::Any)
+}
+
+fn doc_for_keyword(keyword: &str) -> Option<&'static str> {
+    let token = match keyword {
+        "world" => Token::World,
+        "interface" => Token::Interface,
+        "record" => Token::Record,
+        "func" => Token::Func,
+        "use" => Token::Use,
+        "type" => Token::Type,
+        _ => return None,
+    };
+    Some(docs::for_token(&token))
+}
+
+fn markdown(text: &str) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: text.to_owned(),
+    })
+}
+
+/// Every interface name reachable from `workspace`'s merged `Resolve`,
+/// package-qualified (`namespace:name/iface`) the same way a `use` path
+/// spells them.
+fn workspace_interfaces(workspace: &Workspace) -> Vec<String> {
+    let resolve = workspace.resolve();
+    resolve
+        .packages
+        .iter()
+        .flat_map(|(_, package)| {
+            package
+                .interfaces
+                .keys()
+                .map(move |iface| format!("{}/{iface}", package.name))
+        })
+        .collect()
+}
+
+impl super::File {
+    /// Builds the completion list at the cursor, narrowed by
+    /// [`context_at`]: WIT keywords, builtin component-model types, and
+    /// declared names at the top level, builtin types and declared names in
+    /// a type position, and in-scope interface names (including those reachable
+    /// via the workspace's `use` graph) inside a `use` clause.
+    pub fn completions_at(&self, position: Position) -> Result<Vec<CompletionItem>> {
+        let text = self.text();
+        let offset = self.offset_at(position)?;
+        let scan = scan(&text)?;
+        let context = context_at(&text, offset)?;
+
+        let mut items = Vec::new();
+
+        if context == Context::Any {
+            for keyword in KEYWORDS {
+                items.push(CompletionItem {
+                    label: (*keyword).to_owned(),
+                    kind: Some(CompletionItemKind::KEYWORD),
+                    documentation: doc_for_keyword(keyword).map(markdown),
+                    ..Default::default()
+                });
+            }
+        }
+
+        if context != Context::UsePath {
+            for builtin in BUILTIN_TYPES {
+                items.push(CompletionItem {
+                    label: (*builtin).to_owned(),
+                    kind: Some(CompletionItemKind::TYPE_PARAMETER),
+                    ..Default::default()
+                });
+            }
+        }
+
+        let mut seen = HashSet::new();
+        if context != Context::UsePath {
+            for name in scan.declared_names() {
+                if seen.insert(name.to_owned()) {
+                    items.push(CompletionItem {
+                        label: name.to_owned(),
+                        kind: Some(CompletionItemKind::VARIABLE),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        if context == Context::UsePath {
+            if let Some(workspace) = &self.workspace {
+                for name in workspace_interfaces(workspace) {
+                    if seen.insert(name.clone()) {
+                        items.push(CompletionItem {
+                            label: name,
+                            kind: Some(CompletionItemKind::INTERFACE),
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_at_is_type_right_after_a_field_colon() {
+        let text = "record r {\n  a: ";
+        let offset = text.len() as u32;
+        assert_eq!(context_at(text, offset).unwrap(), Context::Type);
+    }
+
+    #[test]
+    fn context_at_ignores_tokens_after_the_cursor() {
+        // A `use` clause later in the document used to leak into the
+        // context of an earlier cursor position because the old scan read
+        // the whole file regardless of `offset`; the fix stops at the
+        // cursor, so this must report `Any`, not `UsePath`.
+        let text = "record r {\n  a: u32,\n};\nuse foo.{bar};";
+        let offset = text.find("a:").unwrap() as u32;
+        assert_eq!(context_at(text, offset).unwrap(), Context::Any);
+    }
+
+    #[test]
+    fn context_at_is_use_path_inside_an_open_use_clause() {
+        let text = "use foo.{bar};\nrecord r { x: u32 }";
+        let offset = text.find("bar").unwrap() as u32;
+        assert_eq!(context_at(text, offset).unwrap(), Context::UsePath);
+    }
+}
@@ -0,0 +1,341 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use wit_parser::ast::lex::{Span, Token, Tokenizer};
+
+use super::super::workspace::{self, Workspace};
+
+/// A named declaration discovered while scanning a file's token stream, e.g. the
+/// `foo` in `record foo { .. }` or `interface foo { .. }`.
+struct Declaration {
+    name: String,
+    span: Span,
+}
+
+/// Keywords that introduce a named declaration: the identifier immediately
+/// following one of these tokens is the thing being declared.
+fn introduces_declaration(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Interface
+            | Token::World
+            | Token::Record
+            | Token::Variant
+            | Token::Enum
+            | Token::Flags
+            | Token::Resource
+            | Token::Type
+    )
+}
+
+fn is_identifier(token: &Token) -> bool {
+    matches!(token, Token::Id | Token::ExplicitId)
+}
+
+/// Scans `text` for every identifier occurrence, tagging the ones that
+/// introduce a declaration (`record foo`, `name: func`, ...) so that
+/// [`super::File::definition_at`] and [`super::File::references_at`] can both
+/// be driven off a single pass over the token stream.
+pub(super) struct Scan {
+    declarations: Vec<Declaration>,
+    occurrences: Vec<(String, Span)>,
+}
+
+impl Scan {
+    /// The names declared in this file, in declaration order; a name
+    /// declared more than once appears once per declaration.
+    pub(super) fn declared_names(&self) -> impl Iterator<Item = &str> {
+        self.declarations.iter().map(|decl| decl.name.as_str())
+    }
+}
+
+pub(super) fn scan(text: &str) -> Result<Scan> {
+    let mut lexer = Tokenizer::new(text, 0)?;
+    let mut declarations = Vec::new();
+    let mut occurrences = Vec::new();
+    let mut prev: Option<Token> = None;
+    // The most recent identifier, and the one right before the latest `:`,
+    // which a following `func` declares (`name: func(...)`).
+    let mut last_ident: Option<(String, Span)> = None;
+    let mut before_colon: Option<(String, Span)> = None;
+
+    while let Some((span, token)) = lexer.next()? {
+        if is_identifier(&token) {
+            let name = text[span.start as usize..span.end as usize].to_owned();
+
+            if prev.as_ref().is_some_and(introduces_declaration) {
+                declarations.push(Declaration {
+                    name: name.clone(),
+                    span,
+                });
+            }
+
+            last_ident = Some((name.clone(), span));
+            occurrences.push((name, span));
+        }
+
+        if matches!(token, Token::Colon) {
+            before_colon = prev.as_ref().is_some_and(is_identifier).then(|| last_ident.clone()).flatten();
+        } else if matches!(token, Token::Func) && matches!(prev, Some(Token::Colon)) {
+            if let Some((name, span)) = before_colon.take() {
+                declarations.push(Declaration { name, span });
+            }
+        }
+
+        if !matches!(token, Token::Whitespace | Token::Comment) {
+            prev = Some(token);
+        }
+    }
+
+    Ok(Scan {
+        declarations,
+        occurrences,
+    })
+}
+
+/// Where an identifier's declaration lives, returned by
+/// [`super::File::definition_at_offset`].
+pub enum Definition {
+    /// Declared in the same document, as a byte span into it.
+    Local(Span),
+    /// Declared in a different file of the same [`Workspace`]. Found by
+    /// asking the workspace's merged `wit_parser::Resolve` which package
+    /// owns the name, then re-scanning that package's own files for the
+    /// declaration -- `Resolve` itself discards source spans once a package
+    /// is folded in, so a byte offset can only come from re-lexing the file
+    /// it actually lives in.
+    Remote(PathBuf, Span),
+}
+
+/// Asks `workspace`'s resolved package graph which package declares an
+/// `interface`, `world`, or type (`record`/`variant`/`enum`/`flags`/
+/// `resource`/`type`) named `name` -- the latter so that following a `use
+/// wasi:clocks/monotonic-clock.{duration}` path lands on `duration` itself,
+/// not just on `monotonic-clock` -- then finds the declaration's span by
+/// re-scanning that package's own files.
+fn resolve_across_workspace(workspace: &Workspace, name: &str) -> Option<(PathBuf, Span)> {
+    let resolve = workspace.resolve();
+
+    let owner = resolve.packages.iter().find_map(|(id, package)| {
+        if package.interfaces.contains_key(name) || package.worlds.contains_key(name) {
+            return Some(id);
+        }
+        let declares_type = package
+            .interfaces
+            .values()
+            .any(|&iface| resolve.interfaces[iface].types.contains_key(name));
+        declares_type.then_some(id)
+    })?;
+
+    for path in workspace.files_for_package(owner) {
+        let Ok(text) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(found) = scan(&text) else {
+            continue;
+        };
+        if let Some(decl) = found.declarations.iter().find(|decl| decl.name == name) {
+            return Some((path.clone(), decl.span));
+        }
+    }
+
+    None
+}
+
+impl super::File {
+    /// Resolves the identifier at `offset` to its [`Definition`]: a local
+    /// span if this document declares it, otherwise a remote file+span if
+    /// this file belongs to a [`Workspace`] whose shared `Resolve` knows
+    /// which other package does -- the case a bare lexical scan of this one
+    /// document can never answer, e.g. following a `use
+    /// wasi:clocks/monotonic-clock.{duration}` path or an interface name
+    /// mentioned in a `world`'s imports/exports.
+    pub fn definition_at_offset(&self, offset: u32) -> Result<Option<Definition>> {
+        let text = self.text();
+        let scan = scan(&text)?;
+
+        let Some((name, _)) = scan
+            .occurrences
+            .iter()
+            .find(|(_, span)| span.start <= offset && offset <= span.end)
+        else {
+            return Ok(None);
+        };
+
+        if let Some(decl) = scan.declarations.iter().find(|decl| &decl.name == name) {
+            return Ok(Some(Definition::Local(decl.span)));
+        }
+
+        if let Some(workspace) = &self.workspace {
+            if let Some((path, span)) = resolve_across_workspace(workspace, name) {
+                return Ok(Some(Definition::Remote(path, span)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Finds every occurrence of the identifier at `offset`, each paired
+    /// with the file it occurs in: this document's own occurrences, plus
+    /// (when this file belongs to a [`Workspace`]) every occurrence of the
+    /// same name in the workspace's other files.
+    pub fn references_at_offset(
+        &self,
+        offset: u32,
+        include_declaration: bool,
+    ) -> Result<Vec<(PathBuf, Span)>> {
+        let text = self.text();
+        let scan = scan(&text)?;
+
+        let Some((name, _)) = scan
+            .occurrences
+            .iter()
+            .find(|(_, span)| span.start <= offset && offset <= span.end)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let declaration_spans: Vec<Span> = scan
+            .declarations
+            .iter()
+            .filter(|decl| &decl.name == name)
+            .map(|decl| decl.span)
+            .collect();
+
+        let mut results: Vec<(PathBuf, Span)> = scan
+            .occurrences
+            .iter()
+            .filter(|(occurrence_name, span)| {
+                occurrence_name == name
+                    && (include_declaration || !declaration_spans.contains(span))
+            })
+            .map(|(_, span)| (self.path.clone(), *span))
+            .collect();
+
+        if let Some(workspace) = &self.workspace {
+            for path in workspace::wit_files_in(workspace.root()) {
+                if path == self.path {
+                    continue;
+                }
+                let Ok(other_text) = std::fs::read_to_string(&path) else {
+                    continue;
+                };
+                let Ok(other_scan) = scan(&other_text) else {
+                    continue;
+                };
+                let other_declaration_spans: Vec<Span> = other_scan
+                    .declarations
+                    .iter()
+                    .filter(|decl| decl.name == name)
+                    .map(|decl| decl.span)
+                    .collect();
+
+                results.extend(
+                    other_scan
+                        .occurrences
+                        .iter()
+                        .filter(|(occurrence_name, span)| {
+                            occurrence_name == name
+                                && (include_declaration
+                                    || !other_declaration_spans.contains(span))
+                        })
+                        .map(|(_, span)| (path.clone(), *span)),
+                );
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use super::super::File;
+
+    /// A directory under the system temp dir unique to this test, holding a
+    /// root package plus one `deps/` package, torn down on drop so repeated
+    /// test runs don't see each other's files.
+    struct TempWorkspaceDir(PathBuf);
+
+    impl TempWorkspaceDir {
+        /// `dep_dir_name` is the directory created under `deps/`, e.g.
+        /// `"mathlib"` for a `deps/mathlib/*.wit` dependency.
+        fn new(name: &str, dep_dir_name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "wit-definition-test-{name}-{}",
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(root.join("deps").join(dep_dir_name)).unwrap();
+            Self(root)
+        }
+    }
+
+    impl Drop for TempWorkspaceDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn definition_at_offset_resolves_interface_declared_in_another_package() {
+        let dir = TempWorkspaceDir::new("cross-package", "mathlib");
+
+        std::fs::write(
+            dir.0.join("deps/mathlib/math.wit"),
+            "package math:mathlib;\n\ninterface calc {\n  add: func(a: u32, b: u32) -> u32;\n}\n",
+        )
+        .unwrap();
+
+        let consumer_path = dir.0.join("consumer.wit");
+        let consumer_text =
+            "package local:consumer;\n\nworld example {\n  import math:mathlib/calc;\n}\n";
+        std::fs::write(&consumer_path, consumer_text).unwrap();
+
+        let workspace = Arc::new(Workspace::load(&dir.0).await.unwrap());
+        let file = File::new(consumer_text).with_context(consumer_path, Some(workspace));
+
+        let offset = consumer_text.find("calc").unwrap() as u32;
+        let definition = file.definition_at_offset(offset).unwrap();
+
+        match definition {
+            Some(Definition::Remote(path, _span)) => {
+                assert!(path.ends_with("math.wit"), "got {}", path.display());
+            }
+            Some(Definition::Local(_)) => panic!("expected a remote definition, got a local one"),
+            None => panic!("expected a remote definition, got none"),
+        }
+    }
+
+    #[tokio::test]
+    async fn definition_at_offset_resolves_type_declared_in_another_package() {
+        let dir = TempWorkspaceDir::new("cross-package-type", "clocks");
+
+        std::fs::write(
+            dir.0.join("deps/clocks/clocks.wit"),
+            "package wasi:clocks;\n\ninterface monotonic-clock {\n  type duration = u64;\n}\n",
+        )
+        .unwrap();
+
+        let consumer_path = dir.0.join("consumer.wit");
+        let consumer_text = "package local:consumer;\n\ninterface conv {\n  use wasi:clocks/monotonic-clock.{duration};\n\n  as-seconds: func(d: duration) -> u64;\n}\n";
+        std::fs::write(&consumer_path, consumer_text).unwrap();
+
+        let workspace = Arc::new(Workspace::load(&dir.0).await.unwrap());
+        let file = File::new(consumer_text).with_context(consumer_path, Some(workspace));
+
+        let offset = consumer_text.find("duration").unwrap() as u32;
+        let definition = file.definition_at_offset(offset).unwrap();
+
+        match definition {
+            Some(Definition::Remote(path, _span)) => {
+                assert!(path.ends_with("clocks.wit"), "got {}", path.display());
+            }
+            Some(Definition::Local(_)) => panic!("expected a remote definition, got a local one"),
+            None => panic!("expected a remote definition, got none"),
+        }
+    }
+}
@@ -1,27 +1,67 @@
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
 
 use anyhow::Result;
 use ropey::Rope;
 use tower_lsp::lsp_types::{
-    Hover, HoverContents, MarkedString, Position, Range, SemanticToken, SemanticTokenType,
-    SemanticTokens,
+    Diagnostic, Hover, HoverContents, MarkedString, Position, Range, SemanticToken,
+    SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensEdit,
+};
+use wit_parser::{
+    ast::lex::{Span, Token, Tokenizer},
+    Resolve,
 };
-use wit_parser::ast::lex::{Span, Token, Tokenizer};
 
+use super::linter;
+use super::workspace::Workspace;
+
+pub(crate) mod completion;
+pub(crate) mod definition;
 pub(crate) mod docs;
+pub(crate) mod symbols;
 pub(crate) mod token;
 
 pub struct File {
     rope: Rope,
+    /// The on-disk path this text came from, if any. Empty for `File`s built
+    /// from a query that isn't tied to one path, e.g. `workspace/symbol`'s
+    /// per-file scan.
+    path: PathBuf,
+    /// The workspace this file was resolved as part of, when `read_file`
+    /// could find or load one covering its path. `definition_at`,
+    /// `references_at`, and `completions_at` consult this instead of
+    /// re-resolving `path` alone once a name isn't found in the document
+    /// itself.
+    workspace: Option<Arc<Workspace>>,
 }
 
 impl File {
     pub fn new(text: impl AsRef<str>) -> Self {
         Self {
             rope: Rope::from_str(text.as_ref()),
+            path: PathBuf::new(),
+            workspace: None,
         }
     }
 
+    /// Attaches the on-disk path and (if one covers it) the shared
+    /// [`Workspace`] this file belongs to, so that cross-file features can
+    /// resolve names this document doesn't declare itself.
+    pub fn with_context(mut self, path: PathBuf, workspace: Option<Arc<Workspace>>) -> Self {
+        self.path = path;
+        self.workspace = workspace;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn text(&self) -> String {
         self.rope.to_string()
     }
@@ -39,6 +79,80 @@ impl File {
         Ok(Range::new(start, end))
     }
 
+    pub fn offset_at(&self, position: Position) -> Result<u32> {
+        let line_char = self.rope.try_line_to_char(position.line as usize)?;
+        Ok(line_char as u32 + position.character)
+    }
+
+    /// Resolves the identifier under `position` to the path and [`Range`] of
+    /// its declaration. The path is this file's own when the declaration is
+    /// local; when it isn't declared here, and this file belongs to a
+    /// [`Workspace`], the shared `Resolve` is consulted to find which other
+    /// file declares it (see [`definition::Definition`]).
+    pub fn definition_at(&self, position: Position) -> Result<Option<(PathBuf, Range)>> {
+        let offset = self.offset_at(position)?;
+        let Some(definition) = self.definition_at_offset(offset)? else {
+            return Ok(None);
+        };
+        match definition {
+            definition::Definition::Local(span) => {
+                Ok(Some((self.path.clone(), self.range_at(&span)?)))
+            }
+            definition::Definition::Remote(path, span) => {
+                let text = std::fs::read_to_string(&path)?;
+                let range = File::new(text).range_at(&span)?;
+                Ok(Some((path, range)))
+            }
+        }
+    }
+
+    /// Finds every reference to the identifier under `position`, in this
+    /// file and, when this file belongs to a [`Workspace`], in every other
+    /// file that workspace resolved alongside it.
+    pub fn references_at(
+        &self,
+        position: Position,
+        include_declaration: bool,
+    ) -> Result<Vec<(PathBuf, Range)>> {
+        let offset = self.offset_at(position)?;
+        self.references_at_offset(offset, include_declaration)?
+            .into_iter()
+            .map(|(path, span)| {
+                let range = if path == self.path {
+                    self.range_at(&span)?
+                } else {
+                    File::new(std::fs::read_to_string(&path)?).range_at(&span)?
+                };
+                Ok((path, range))
+            })
+            .collect()
+    }
+
+    /// Resolves and reports any syntax or name-resolution error
+    /// `wit_parser` raises, translated to an LSP [`Diagnostic`] from the
+    /// `--> file:line:col` location in its rendered error message.
+    ///
+    /// When this file belongs to a [`Workspace`], resolution runs against
+    /// that workspace's merged `Resolve` so `use` paths into `deps/` don't
+    /// report as unresolved here just because this one file is parsed in
+    /// isolation; otherwise this file is resolved on its own.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        if let Some(workspace) = &self.workspace {
+            return match Workspace::check(workspace.root()) {
+                Ok(()) => Vec::new(),
+                // `{:#}` includes the underlying `wit_parser` error, not just
+                // the "failed to resolve WIT package at ..." context around it.
+                Err(err) => linter::diagnostics_from_error(&format!("{err:#}")),
+            };
+        }
+
+        let mut resolve = Resolve::default();
+        match resolve.push_str(&self.path, &self.text()) {
+            Ok(_) => Vec::new(),
+            Err(err) => linter::diagnostics_from_error(&err.to_string()),
+        }
+    }
+
     pub fn hover_at(&self, position: Position) -> Result<Hover> {
         let text = self.text();
         let mut lexer = Tokenizer::new(&text, 0)?;
@@ -57,6 +171,19 @@ impl File {
     }
 
     pub fn semantic_tokens(&self) -> SemanticTokens {
+        self.semantic_tokens_filtered(|_| true)
+    }
+
+    /// Same as [`Self::semantic_tokens`] but limited to tokens that start on
+    /// a line within `range`, for `textDocument/semanticTokens/range` on
+    /// large files where tokenizing the whole document is wasted work.
+    pub fn semantic_tokens_range(&self, range: Range) -> SemanticTokens {
+        self.semantic_tokens_filtered(|token_range| {
+            token_range.start.line >= range.start.line && token_range.start.line <= range.end.line
+        })
+    }
+
+    fn semantic_tokens_filtered(&self, keep: impl Fn(Range) -> bool) -> SemanticTokens {
         let id = TOKEN_RESULT_COUNTER
             .fetch_add(1, Ordering::SeqCst)
             .to_string();
@@ -69,7 +196,8 @@ impl File {
 
         while let Ok(Some((span, token))) = lexer.next() {
             let Ok(range) = self.range_at(&span) else { continue };
-            builder.push_token(&range, &token);
+            let token_text = &text[span.start as usize..span.end as usize];
+            builder.push_token(&range, &token, token_text, keep(range));
         }
 
         builder.build()
@@ -82,6 +210,50 @@ fn range_contains(range: Range, position: Position) -> bool {
         && range.end.character >= position.character
 }
 
+/// Flattens a [`SemanticTokens`] result into the raw `u32` quintuples the LSP
+/// wire format (and [`diff_semantic_tokens`]) operates on.
+pub fn flatten_semantic_tokens(tokens: &SemanticTokens) -> Vec<u32> {
+    tokens
+        .data
+        .iter()
+        .flat_map(|token| {
+            [
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ]
+        })
+        .collect()
+}
+
+/// Diffs two flattened token streams by common prefix/suffix, the same
+/// strategy editors themselves use to turn a full re-tokenization into a
+/// `textDocument/semanticTokens/full/delta` edit.
+pub fn diff_semantic_tokens(previous: &[u32], current: &[u32]) -> Vec<SemanticTokensEdit> {
+    let prefix = previous
+        .iter()
+        .zip(current.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let max_suffix = previous.len().min(current.len()) - prefix;
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(max_suffix);
+
+    vec![SemanticTokensEdit {
+        start: prefix as u32,
+        delete_count: (previous.len() - prefix - suffix) as u32,
+        data: Some(current[prefix..current.len() - suffix].to_vec()),
+    }]
+}
+
 static TOKEN_RESULT_COUNTER: AtomicU32 = AtomicU32::new(1);
 
 pub struct SemanticTokensBuilder {
@@ -89,6 +261,14 @@ pub struct SemanticTokensBuilder {
     prev_line: u32,
     prev_char: u32,
     data: Vec<SemanticToken>,
+    /// Set by a `type`/`record`/`variant`/`enum`/`flags`/`resource`/
+    /// `interface`/`world` keyword and cleared by the next identifier, which
+    /// names that declaration and is tagged with the `declaration` modifier.
+    pending_declaration: bool,
+    /// Set between the `package` keyword and its closing `;`, so the
+    /// namespace and name segments of `package foo:bar@1.0.0;` are tagged
+    /// `NAMESPACE` rather than the generic `VARIABLE` type.
+    in_package_header: bool,
 }
 
 impl SemanticTokensBuilder {
@@ -98,10 +278,21 @@ impl SemanticTokensBuilder {
             prev_line: 0,
             prev_char: 0,
             data: Default::default(),
+            pending_declaration: false,
+            in_package_header: false,
         }
     }
 
     pub fn push(&mut self, range: &Range, token: &SemanticTokenType) {
+        self.push_with_modifiers(range, token, 0);
+    }
+
+    pub fn push_with_modifiers(
+        &mut self,
+        range: &Range,
+        token: &SemanticTokenType,
+        modifiers: u32,
+    ) {
         let mut delta_line = range.start.line;
         let mut delta_start = range.start.character;
 
@@ -117,7 +308,7 @@ impl SemanticTokensBuilder {
             delta_start,
             length: range.end.character - range.start.character,
             token_type: token::type_index(token),
-            token_modifiers_bitset: 0,
+            token_modifiers_bitset: modifiers,
         };
 
         self.data.push(token);
@@ -126,21 +317,28 @@ impl SemanticTokensBuilder {
         self.prev_char = range.start.character;
     }
 
-    pub fn push_token(&mut self, range: &Range, token: &Token) {
+    /// Classifies and (if `emit` is set) pushes `token`. `emit` is threaded
+    /// through separately from the match so that
+    /// [`File::semantic_tokens_range`] still observes every token in the
+    /// file and keeps its lexical context (declaration/package-header
+    /// tracking) correct even though it only renders a subrange.
+    pub fn push_token(&mut self, range: &Range, token: &Token, text: &str, emit: bool) {
         match token {
             Token::Whitespace => {}
             Token::Comment => {
-                self.push(range, &SemanticTokenType::COMMENT);
+                let modifiers = if text.starts_with("///") {
+                    token::modifier_bitset(SemanticTokenModifier::DOCUMENTATION)
+                } else {
+                    0
+                };
+                if emit {
+                    self.push_with_modifiers(range, &SemanticTokenType::COMMENT, modifiers);
+                }
             }
             Token::Equals
             | Token::Comma
             | Token::Colon
             | Token::Period
-            | Token::Semicolon
-            | Token::LeftParen
-            | Token::RightParen
-            | Token::LeftBrace
-            | Token::RightBrace
             | Token::LessThan
             | Token::GreaterThan
             | Token::RArrow
@@ -148,30 +346,48 @@ impl SemanticTokensBuilder {
             | Token::At
             | Token::Slash
             | Token::Plus
-            | Token::Minus => {
-                self.push(range, &SemanticTokenType::OPERATOR);
+            | Token::Minus
+            | Token::LeftParen
+            | Token::RightParen
+            | Token::LeftBrace
+            | Token::RightBrace => {
+                if emit {
+                    self.push(range, &SemanticTokenType::OPERATOR);
+                }
+            }
+
+            Token::Semicolon => {
+                self.in_package_header = false;
+                if emit {
+                    self.push(range, &SemanticTokenType::OPERATOR);
+                }
+            }
+
+            Token::Package => {
+                self.in_package_header = true;
+                if emit {
+                    self.push(range, &SemanticTokenType::KEYWORD);
+                }
+            }
+
+            Token::Interface | Token::World | Token::Type | Token::Resource | Token::Record
+            | Token::Flags | Token::Variant | Token::Enum | Token::Union => {
+                self.pending_declaration = true;
+                if emit {
+                    self.push(range, &SemanticTokenType::KEYWORD);
+                }
             }
 
-            Token::Include
-            | Token::Package
-            | Token::Interface
-            | Token::Import
-            | Token::Export
-            | Token::World
-            | Token::Use
-            | Token::Type
-            | Token::Func
-            | Token::Resource
-            | Token::Record
-            | Token::Flags
-            | Token::Variant
-            | Token::Enum
-            | Token::Union => {
-                self.push(range, &SemanticTokenType::KEYWORD);
+            Token::Include | Token::Import | Token::Export | Token::Use | Token::Func => {
+                if emit {
+                    self.push(range, &SemanticTokenType::KEYWORD);
+                }
             }
 
             Token::With | Token::As | Token::From_ | Token::Static | Token::Shared => {
-                self.push(range, &SemanticTokenType::MODIFIER);
+                if emit {
+                    self.push(range, &SemanticTokenType::MODIFIER);
+                }
             }
 
             Token::U8
@@ -193,15 +409,38 @@ impl SemanticTokensBuilder {
             | Token::Stream
             | Token::List
             | Token::Tuple => {
-                self.push(range, &SemanticTokenType::TYPE);
+                if emit {
+                    self.push_with_modifiers(
+                        range,
+                        &SemanticTokenType::TYPE,
+                        token::modifier_bitset(SemanticTokenModifier::DEFAULT_LIBRARY),
+                    );
+                }
             }
 
             Token::Underscore | Token::Id | Token::ExplicitId => {
-                self.push(range, &SemanticTokenType::VARIABLE);
+                if self.in_package_header {
+                    if emit {
+                        self.push(range, &SemanticTokenType::NAMESPACE);
+                    }
+                } else if self.pending_declaration {
+                    self.pending_declaration = false;
+                    if emit {
+                        self.push_with_modifiers(
+                            range,
+                            &SemanticTokenType::VARIABLE,
+                            token::modifier_bitset(SemanticTokenModifier::DECLARATION),
+                        );
+                    }
+                } else if emit {
+                    self.push(range, &SemanticTokenType::VARIABLE);
+                }
             }
 
             Token::Integer => {
-                self.push(range, &SemanticTokenType::NUMBER);
+                if emit {
+                    self.push(range, &SemanticTokenType::NUMBER);
+                }
             }
         };
     }
@@ -0,0 +1,378 @@
+use anyhow::Result;
+use tower_lsp::lsp_types::{DocumentSymbol, Range, SymbolKind};
+use wit_parser::ast::lex::{Span, Token, Tokenizer};
+
+/// A declaration discovered while walking the token stream, paired with the
+/// [`SymbolKind`] it should render as in an outline, and any declarations or
+/// members nested directly inside its body.
+struct Entry {
+    name: String,
+    kind: SymbolKind,
+    span: Span,
+    children: Vec<Entry>,
+}
+
+/// How a container's direct members are written, so the scanner knows what
+/// token pattern marks one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MemberSyntax {
+    /// `world`/`interface`/`resource`: members are `name: func(...)`.
+    Function,
+    /// `record`: members are `name: type`.
+    TypedField,
+    /// `flags`/`variant`/`union`/`enum`: members are a bare name, optionally
+    /// followed by a parenthesized payload type (`variant`/`union` only).
+    Case,
+    /// `type`: a one-line alias with no body.
+    None,
+}
+
+fn classify(token: &Token) -> Option<(SymbolKind, MemberSyntax)> {
+    match token {
+        Token::World => Some((SymbolKind::MODULE, MemberSyntax::Function)),
+        Token::Interface => Some((SymbolKind::INTERFACE, MemberSyntax::Function)),
+        Token::Resource => Some((SymbolKind::CLASS, MemberSyntax::Function)),
+        Token::Record => Some((SymbolKind::STRUCT, MemberSyntax::TypedField)),
+        Token::Flags => Some((SymbolKind::STRUCT, MemberSyntax::Case)),
+        Token::Variant | Token::Union | Token::Enum => {
+            Some((SymbolKind::ENUM, MemberSyntax::Case))
+        }
+        Token::Type => Some((SymbolKind::TYPE_PARAMETER, MemberSyntax::None)),
+        _ => None,
+    }
+}
+
+fn is_identifier(token: &Token) -> bool {
+    matches!(token, Token::Id | Token::ExplicitId)
+}
+
+struct Frame {
+    kind: SymbolKind,
+    syntax: MemberSyntax,
+    name: String,
+    span: Span,
+    children: Vec<Entry>,
+    brace_depth: i32,
+}
+
+fn attach(stack: &mut [Frame], roots: &mut Vec<Entry>, entry: Entry) {
+    match stack.last_mut() {
+        Some(frame) => frame.children.push(entry),
+        None => roots.push(entry),
+    }
+}
+
+/// Finishes whatever member name is pending (a record field or a
+/// flags/variant/union/enum case) and attaches it to the innermost frame.
+fn finalize_member(stack: &mut [Frame], member_name: &mut Option<(String, Span)>) {
+    let Some((name, span)) = member_name.take() else {
+        return;
+    };
+    let Some(frame) = stack.last_mut() else {
+        return;
+    };
+    let kind = match frame.syntax {
+        MemberSyntax::TypedField => SymbolKind::FIELD,
+        MemberSyntax::Case => SymbolKind::ENUM_MEMBER,
+        MemberSyntax::Function | MemberSyntax::None => return,
+    };
+    frame.children.push(Entry {
+        name,
+        kind,
+        span,
+        children: Vec::new(),
+    });
+}
+
+/// Scans `text` for `world`/`interface`/`record`/`variant`/`union`/`enum`/
+/// `flags`/`resource`/`type` declarations, nesting each one under its
+/// enclosing declaration, and additionally walks each container's body for
+/// its members: `func` items inside `world`/`interface`/`resource`, fields
+/// inside `record`, cases inside `flags`/`variant`/`union`/`enum`, and a
+/// world's bare `import`/`export` statements (`import wasi:io/streams;`,
+/// not the named-function-or-type form, which is already a member via the
+/// usual `name: func(...)` handling).
+///
+/// This is still a lexical scan rather than a semantic one, so a `resource`
+/// constructor (which has no `func` keyword) isn't picked out as a member.
+fn entries(text: &str) -> Result<Vec<Entry>> {
+    let mut lexer = Tokenizer::new(text, 0)?;
+
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut roots: Vec<Entry> = Vec::new();
+    let mut brace_depth = 0i32;
+    let mut paren_depth = 0i32;
+
+    let mut pending_kind: Option<(SymbolKind, MemberSyntax)> = None;
+    let mut pending_decl: Option<(SymbolKind, MemberSyntax, String, Span)> = None;
+    let mut member_name: Option<(String, Span)> = None;
+    let mut awaiting_func = false;
+    let mut suppress_capture = false;
+    // Set by a bare `import`/`export` keyword (not one later followed by a
+    // `:` naming a func or inline type, which the existing member-syntax
+    // handling already covers) to the byte offset right after the keyword,
+    // so the whole `use`-style path up to the closing `;` -- e.g.
+    // `wasi:clocks/monotonic-clock` -- can be sliced out as one entry's
+    // name once it's known the statement ended without a `:`.
+    let mut import_export_start: Option<u32> = None;
+    let mut saw_colon_since_import = false;
+
+    while let Some((span, token)) = lexer.next()? {
+        if matches!(token, Token::Whitespace | Token::Comment) {
+            continue;
+        }
+
+        match token {
+            Token::LeftParen => {
+                paren_depth += 1;
+                continue;
+            }
+            Token::RightParen => {
+                paren_depth = (paren_depth - 1).max(0);
+                continue;
+            }
+            _ => {}
+        }
+
+        if paren_depth > 0 {
+            continue;
+        }
+
+        match token {
+            Token::LeftBrace => {
+                brace_depth += 1;
+                if let Some((kind, syntax, name, name_span)) = pending_decl.take() {
+                    stack.push(Frame {
+                        kind,
+                        syntax,
+                        name,
+                        span: name_span,
+                        children: Vec::new(),
+                        brace_depth,
+                    });
+                }
+                member_name = None;
+                awaiting_func = false;
+                suppress_capture = false;
+                import_export_start = None;
+                continue;
+            }
+            Token::RightBrace => {
+                import_export_start = None;
+                finalize_member(&mut stack, &mut member_name);
+                if stack.last().is_some_and(|frame| frame.brace_depth == brace_depth) {
+                    let frame = stack.pop().unwrap();
+                    attach(
+                        &mut stack,
+                        &mut roots,
+                        Entry {
+                            name: frame.name,
+                            kind: frame.kind,
+                            span: frame.span,
+                            children: frame.children,
+                        },
+                    );
+                }
+                brace_depth -= 1;
+                awaiting_func = false;
+                suppress_capture = false;
+                continue;
+            }
+            Token::Comma | Token::Semicolon => {
+                if let Some((kind, _syntax, name, name_span)) = pending_decl.take() {
+                    attach(
+                        &mut stack,
+                        &mut roots,
+                        Entry {
+                            name,
+                            kind,
+                            span: name_span,
+                            children: Vec::new(),
+                        },
+                    );
+                }
+                if let Some(start) = import_export_start.take() {
+                    if !saw_colon_since_import {
+                        if let Some((_, name_span)) = member_name.take() {
+                            let name = text[start as usize..name_span.end as usize]
+                                .trim()
+                                .to_owned();
+                            if !name.is_empty() {
+                                attach(
+                                    &mut stack,
+                                    &mut roots,
+                                    Entry {
+                                        name,
+                                        kind: SymbolKind::INTERFACE,
+                                        span: Span {
+                                            start,
+                                            end: name_span.end,
+                                        },
+                                        children: Vec::new(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+                finalize_member(&mut stack, &mut member_name);
+                awaiting_func = false;
+                suppress_capture = false;
+                continue;
+            }
+            Token::Import | Token::Export => {
+                import_export_start = Some(span.end);
+                saw_colon_since_import = false;
+                member_name = None;
+                continue;
+            }
+            _ => {}
+        }
+
+        if suppress_capture {
+            continue;
+        }
+
+        if awaiting_func {
+            awaiting_func = false;
+            if matches!(token, Token::Func) {
+                saw_colon_since_import = true;
+                if let Some((name, name_span)) = member_name.take() {
+                    let kind = if stack.last().map(|frame| frame.kind) == Some(SymbolKind::CLASS) {
+                        SymbolKind::METHOD
+                    } else {
+                        SymbolKind::FUNCTION
+                    };
+                    attach(
+                        &mut stack,
+                        &mut roots,
+                        Entry {
+                            name,
+                            kind,
+                            span: name_span,
+                            children: Vec::new(),
+                        },
+                    );
+                }
+                suppress_capture = true;
+            }
+            continue;
+        }
+
+        if let Some((kind, syntax)) = pending_kind.take() {
+            if is_identifier(&token) {
+                let name = text[span.start as usize..span.end as usize].to_owned();
+                pending_decl = Some((kind, syntax, name, span));
+                continue;
+            }
+        }
+
+        if let Some((kind, syntax)) = classify(&token) {
+            pending_kind = Some((kind, syntax));
+            member_name = None;
+            continue;
+        }
+
+        if matches!(token, Token::Colon) {
+            if let Some(frame) = stack.last() {
+                match frame.syntax {
+                    MemberSyntax::Function if member_name.is_some() => awaiting_func = true,
+                    MemberSyntax::TypedField if member_name.is_some() => suppress_capture = true,
+                    _ => {}
+                }
+            }
+            continue;
+        }
+
+        if is_identifier(&token) {
+            let name = text[span.start as usize..span.end as usize].to_owned();
+            member_name = Some((name, span));
+        }
+    }
+
+    Ok(roots)
+}
+
+impl super::File {
+    /// Builds an outline of the file's `interface`/`world` declarations, the
+    /// types nested inside them, and each declaration's own members (funcs,
+    /// record fields, variant/enum cases, resource methods, and a world's
+    /// own `import`/`export` references). Cross-file go-to-definition on one
+    /// of those references is handled separately by
+    /// [`super::File::definition_at`], which consults the workspace's
+    /// shared `Resolve` once a name isn't declared in this document.
+    pub fn document_symbols(&self) -> Result<Vec<DocumentSymbol>> {
+        let text = self.text();
+        entries(&text)?
+            .into_iter()
+            .map(|entry| self.to_document_symbol(entry))
+            .collect()
+    }
+
+    fn to_document_symbol(&self, entry: Entry) -> Result<DocumentSymbol> {
+        let range = self.range_at(&entry.span)?;
+        let children = entry
+            .children
+            .into_iter()
+            .map(|child| self.to_document_symbol(child))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(DocumentSymbol {
+            children: (!children.is_empty()).then_some(children),
+            ..leaf_symbol(entry.name, entry.kind, range)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_nests_bare_import_and_named_export_as_members() {
+        let text = r#"package local:test;
+
+world example {
+  import wasi:io/streams;
+  export run: func();
+}
+"#;
+
+        let roots = entries(text).unwrap();
+        let world = roots
+            .iter()
+            .find(|entry| entry.name == "example")
+            .expect("world entry");
+
+        let names: Vec<&str> = world.children.iter().map(|child| child.name.as_str()).collect();
+        assert!(
+            names.contains(&"wasi:io/streams"),
+            "expected the bare import to be nested under the world, got {names:?}"
+        );
+        assert!(
+            names.contains(&"run"),
+            "expected the named export to still be nested under the world, got {names:?}"
+        );
+
+        let import = world
+            .children
+            .iter()
+            .find(|child| child.name == "wasi:io/streams")
+            .unwrap();
+        assert_eq!(import.kind, SymbolKind::INTERFACE);
+    }
+}
+
+#[allow(deprecated)]
+fn leaf_symbol(name: String, kind: SymbolKind, range: Range) -> DocumentSymbol {
+    DocumentSymbol {
+        name,
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range: range,
+        children: None,
+    }
+}
@@ -230,7 +230,7 @@ impl Token<'_> {
                     delta_line: 0,
                     delta_start: 0,
                     length: package.len() as u32,
-                    token_type: token_type_index(SemanticTokenType::NAMESPACE),
+                    token_type: type_index(SemanticTokenType::NAMESPACE),
                     token_modifiers_bitset: 0
                 });
                 tokens
@@ -238,12 +238,6 @@ impl Token<'_> {
             _ => Vec::new()
         }
     }
-
-    // pub fn token_modifiers_bitset(&self) -> u32 {
-    //     self.modifiers().into_iter()
-    //     .map(|modifier| 1 << MODIFIERS.iter().position(|m| m == &modifier).unwrap_or(0))
-    //     .sum()
-    // }
 }
 
 impl Display for Token<'_> {
@@ -342,10 +336,16 @@ world hello {
 use tower_lsp::lsp_types::SemanticTokensLegend;
 
 
-pub fn token_type_index(token_type: SemanticTokenType) -> u32 {
+pub fn type_index(token_type: SemanticTokenType) -> u32 {
     TYPES.iter().position(|t| t == &token_type).unwrap_or(0) as u32
 }
 
+/// Computes the bitset for a single [`SemanticTokenModifier`], for the
+/// `token_modifiers_bitset` field of a [`SemanticToken`].
+pub fn modifier_bitset(modifier: SemanticTokenModifier) -> u32 {
+    1 << MODIFIERS.iter().position(|m| m == &modifier).unwrap_or(0)
+}
+
 pub const TYPES: [SemanticTokenType; 23] = [
     SemanticTokenType::KEYWORD,
     SemanticTokenType::NAMESPACE,
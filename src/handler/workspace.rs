@@ -0,0 +1,349 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use wit_parser::{PackageId, Resolve};
+
+use super::registry::{self, Registry};
+
+/// Name of the environment variable pointing at a registry to fetch missing
+/// `use` dependencies from. Unset by default, in which case unresolved
+/// dependencies are left as resolution errors, same as before.
+///
+/// `pub(crate)` so that `Handler::fetch_dependencies` (the manual
+/// `wit.fetchDependencies` command) can read it too, rather than only the
+/// automatic fallback in [`Workspace::resolve_with_registry_fallback`].
+pub(crate) const REGISTRY_URL_ENV: &str = "WIT_REGISTRY_URL";
+
+/// One dependency recorded in `wkg.lock`, keyed by package name so that
+/// re-resolving the workspace can detect when an on-disk dependency has
+/// changed underneath it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub digest: String,
+}
+
+/// A `wkg.lock`-style record of every dependency package a workspace was
+/// resolved against, so that resolution is reproducible and staleness can be
+/// detected without re-parsing everything.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    pub const FILE_NAME: &'static str = "wkg.lock";
+
+    pub async fn read(root: &Path) -> Result<Self> {
+        let path = root.join(Self::FILE_NAME);
+        let Ok(contents) = tokio::fs::read_to_string(&path).await else {
+            return Ok(Self::default());
+        };
+        toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub async fn write(&self, root: &Path) -> Result<()> {
+        let path = root.join(Self::FILE_NAME);
+        let contents = toml::to_string_pretty(self)?;
+        tokio::fs::write(path, contents).await?;
+        Ok(())
+    }
+
+    fn digest_for(&self, name: &str) -> Option<&str> {
+        self.packages
+            .iter()
+            .find(|package| package.name == name)
+            .map(|package| package.digest.as_str())
+    }
+}
+
+/// A simple, dependency-free content digest. This only needs to detect
+/// "did this dependency change on disk", not resist tampering.
+fn digest(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// One directory under `deps/` that was folded into the workspace's
+/// [`Resolve`], along with the digest of its concatenated WIT sources.
+struct Dependency {
+    name: String,
+    digest: String,
+}
+
+/// The package graph for a workspace root: every `*.wit` file directly in the
+/// root plus every package under `deps/`, resolved together so that
+/// cross-package `use` references stop reporting spurious errors.
+///
+/// Callers that need to go from a source file back into this graph (or vice
+/// versa) -- `hover`, `goto_definition`, completion, and diagnostics -- use
+/// [`Workspace::files_for_package`]/[`Workspace::contains_file`] rather than
+/// re-resolving that one file in isolation.
+pub struct Workspace {
+    root: PathBuf,
+    resolve: Resolve,
+    main: PackageId,
+    dependencies: Vec<Dependency>,
+    /// The digest of the root's own `*.wit` sources (not `deps/`), computed
+    /// the same way as each dependency's. Nothing compares it: both
+    /// [`Workspace::stale_dependencies`] and `wkg.lock` cover `deps/` only.
+    root_digest: String,
+    /// Every `*.wit` file folded into `resolve`, keyed by the package it
+    /// belongs to.
+    package_files: HashMap<PackageId, Vec<PathBuf>>,
+    /// `mtime` of every file in `package_files` as of the last resolve, so
+    /// [`Workspace::is_stale`] can check "did anything change" with cheap
+    /// `stat` calls instead of re-resolving the whole package graph.
+    file_mtimes: HashMap<PathBuf, SystemTime>,
+}
+
+impl Workspace {
+    /// Scans `root` for WIT sources and a `deps/` tree, resolving all of it
+    /// into one [`Resolve`]. If a `use` references a package missing from
+    /// `deps/` and [`REGISTRY_URL_ENV`] is set, fetches it from that
+    /// registry and retries resolution once.
+    pub async fn load(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        Self::resolve_with_registry_fallback(&root).await
+    }
+
+    async fn resolve_with_registry_fallback(root: &Path) -> Result<Self> {
+        match Self::try_load(root) {
+            Ok(workspace) => Ok(workspace),
+            Err(err) => {
+                let Ok(registry_url) = std::env::var(REGISTRY_URL_ENV) else {
+                    return Err(err);
+                };
+                // `try_load` always wraps the underlying `wit_parser` error
+                // in a `with_context` of its own ("failed to resolve WIT
+                // package at ..."), so `err.to_string()` only ever shows
+                // that outer message. The "package not found: ..." cause is
+                // further down the chain -- format with the alternate
+                // (`{:#}`) flag to walk it and see every `.context()` layer
+                // concatenated.
+                let message = format!("{err:#}");
+                let Some(package) = registry::missing_package_from_error(&message) else {
+                    return Err(err);
+                };
+
+                let deps_dir = root.join("deps");
+                Registry::new(registry_url)
+                    .fetch_into(&deps_dir, package)
+                    .await
+                    .with_context(|| format!("failed to fetch missing dependency `{package}`"))?;
+
+                Self::try_load(root)
+            }
+        }
+    }
+
+    /// Re-resolves `root` from scratch purely to surface a resolution
+    /// error, without caching anything. Used for `diagnostics`, which needs
+    /// an answer for the workspace's *current* on-disk state on every
+    /// keystroke rather than whatever was cached at `initialize` time.
+    pub fn check(root: &Path) -> Result<()> {
+        Self::try_load(root).map(|_| ())
+    }
+
+    fn try_load(root: &Path) -> Result<Self> {
+        let root = root.to_path_buf();
+        let mut resolve = Resolve::default();
+
+        let (main, main_files) = resolve
+            .push_dir(&root)
+            .with_context(|| format!("failed to resolve WIT package at {}", root.display()))?;
+        let root_digest = digest_of_files(&main_files)?;
+
+        let mut package_files = HashMap::new();
+        package_files.insert(main, main_files);
+
+        let mut dependencies = Vec::new();
+        let deps_dir = root.join("deps");
+        if deps_dir.is_dir() {
+            for entry in std::fs::read_dir(&deps_dir)
+                .with_context(|| format!("failed to read {}", deps_dir.display()))?
+            {
+                let entry = entry?;
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+
+                let (id, files) = resolve
+                    .push_dir(&path)
+                    .with_context(|| format!("failed to resolve dependency at {}", path.display()))?;
+
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                dependencies.push(Dependency {
+                    name,
+                    digest: digest_of_files(&files)?,
+                });
+                package_files.insert(id, files);
+            }
+        }
+
+        let mut file_mtimes = HashMap::new();
+        for file in package_files.values().flatten() {
+            file_mtimes.insert(file.clone(), mtime_of(file)?);
+        }
+
+        Ok(Self {
+            root,
+            resolve,
+            main,
+            dependencies,
+            root_digest,
+            package_files,
+            file_mtimes,
+        })
+    }
+
+    pub fn resolve(&self) -> &Resolve {
+        &self.resolve
+    }
+
+    pub fn main_package(&self) -> PackageId {
+        self.main
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The `*.wit` files folded into `package`, e.g. for re-scanning a
+    /// dependency's declarations lexically once [`Self::resolve`] has said
+    /// which package owns a name.
+    pub fn files_for_package(&self, package: PackageId) -> &[PathBuf] {
+        self.package_files
+            .get(&package)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Whether `path` was folded into this workspace's [`Resolve`], i.e.
+    /// whether a feature touching `path` can reuse this workspace instead of
+    /// parsing `path` on its own.
+    pub fn contains_file(&self, path: &Path) -> bool {
+        self.package_files
+            .values()
+            .any(|files| files.iter().any(|file| file == path))
+    }
+
+    /// Checks whether this `Workspace` still matches what's on disk using
+    /// only directory listings and `stat` calls -- no re-resolving and no
+    /// re-reading file contents -- so a caller holding a cached instance can
+    /// cheaply tell whether it needs to reload before trusting `resolve()`
+    /// again. A full re-resolve only happens once this returns `true`.
+    pub fn is_stale(&self) -> Result<bool> {
+        let current_files = wit_files_in(&self.root);
+        if current_files.len() != self.file_mtimes.len() {
+            return Ok(true);
+        }
+
+        for file in &current_files {
+            let Some(recorded) = self.file_mtimes.get(file) else {
+                // A file that wasn't part of the resolved graph at all
+                // (e.g. replacing one `*.wit` file with another of the same
+                // count) is itself a change.
+                return Ok(true);
+            };
+            match mtime_of(file) {
+                Ok(modified) if modified == *recorded => {}
+                _ => return Ok(true),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Compares the digest of each resolved dependency against `wkg.lock`,
+    /// returning the names of dependencies that changed on disk since the
+    /// lockfile was written.
+    pub async fn stale_dependencies(&self) -> Result<Vec<String>> {
+        let lockfile = Lockfile::read(&self.root).await?;
+        Ok(self
+            .dependencies
+            .iter()
+            .filter(|dep| lockfile.digest_for(&dep.name) != Some(dep.digest.as_str()))
+            .map(|dep| dep.name.clone())
+            .collect())
+    }
+
+    /// Writes `wkg.lock` reflecting the dependencies resolved into this
+    /// workspace.
+    pub async fn write_lockfile(&self) -> Result<()> {
+        let lockfile = Lockfile {
+            packages: self
+                .dependencies
+                .iter()
+                .map(|dep| LockedPackage {
+                    name: dep.name.clone(),
+                    version: None,
+                    digest: dep.digest.clone(),
+                })
+                .collect(),
+        };
+        lockfile.write(&self.root).await
+    }
+}
+
+/// Every `*.wit` file directly under `root` plus every `*.wit` file one
+/// level inside `root/deps/<package>/`, for workspace-wide features (symbol
+/// search) that need to visit each file in the resolved package graph.
+pub fn wit_files_in(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_wit_files(root, &mut files);
+
+    let deps_dir = root.join("deps");
+    if let Ok(entries) = std::fs::read_dir(&deps_dir) {
+        for entry in entries.flatten() {
+            if entry.path().is_dir() {
+                collect_wit_files(&entry.path(), &mut files);
+            }
+        }
+    }
+
+    files
+}
+
+fn collect_wit_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "wit") {
+            out.push(path);
+        }
+    }
+}
+
+fn digest_of_files(files: &[PathBuf]) -> Result<String> {
+    let mut bytes = Vec::new();
+    for file in files {
+        bytes.extend(std::fs::read(file)?);
+    }
+    Ok(digest(&bytes))
+}
+
+/// Cheap staleness signal for a single file: its last-modified time, without
+/// reading its contents.
+fn mtime_of(file: &Path) -> Result<SystemTime> {
+    std::fs::metadata(file)
+        .with_context(|| format!("failed to stat {}", file.display()))?
+        .modified()
+        .with_context(|| format!("no mtime available for {}", file.display()))
+}
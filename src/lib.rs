@@ -3,8 +3,13 @@ use tower_lsp::{
     jsonrpc::Result,
     lsp_types::{
         DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-        DidSaveTextDocumentParams, Hover, HoverParams, InitializeParams, InitializeResult,
-        InitializedParams, SemanticTokensParams, SemanticTokensResult, WillSaveTextDocumentParams,
+        CodeActionParams, CodeActionResponse, CompletionParams, CompletionResponse,
+        DidSaveTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse,
+        ExecuteCommandParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams,
+        InitializeParams, InitializeResult, InitializedParams, Location, ReferenceParams,
+        SemanticTokensDeltaParams, SemanticTokensFullDeltaResult, SemanticTokensParams,
+        SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
+        SymbolInformation, WillSaveTextDocumentParams, WorkspaceSymbolParams,
     },
     Client, LanguageServer,
 };
@@ -12,6 +17,7 @@ use tower_lsp::{
 /// The main entry point for the Wit LSP.
 mod handler;
 use handler::Handler;
+pub use handler::{emit_sarif, emit_sarif_workspace};
 
 pub struct WitLsp {
     handler: Handler,
@@ -60,6 +66,46 @@ impl LanguageServer for WitLsp {
         Ok(self.handler.hover(params).await)
     }
 
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        Ok(self.handler.goto_definition(params).await)
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        Ok(self.handler.references(params).await)
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        Ok(self.handler.completion(params).await)
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        Ok(self.handler.code_action(params).await)
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        Ok(self.handler.document_symbol(params).await)
+    }
+
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        Ok(self.handler.execute_command(params).await)
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        Ok(self.handler.symbol(params).await)
+    }
+
     async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
@@ -67,6 +113,20 @@ impl LanguageServer for WitLsp {
         Ok(Some(self.handler.semantic_tokens_full(params).await))
     }
 
+    async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        Ok(Some(self.handler.semantic_tokens_full_delta(params).await))
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        Ok(Some(self.handler.semantic_tokens_range(params).await))
+    }
+
     async fn shutdown(&self) -> Result<()> {
         self.handler.shutdown().await;
         Ok(())
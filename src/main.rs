@@ -1,6 +1,8 @@
 #![warn(clippy::pedantic)]
 #![warn(clippy::nursery)]
 
+use std::path::PathBuf;
+
 use tower_lsp::{LspService, Server};
 use wit_lsp::WitLsp;
 
@@ -12,7 +14,65 @@ async fn start() {
     server.serve(service).await;
 }
 
+/// Runs `wasm-tools component wit` over each of `dirs` and prints the
+/// combined diagnostics as a SARIF 2.1.0 log, for CI code-scanning
+/// integration. Exits non-zero if any directory failed to lint.
+async fn sarif(dirs: Vec<PathBuf>) {
+    match wit_lsp::emit_sarif(&dirs).await {
+        Ok(log) => println!("{}", serde_json::to_string_pretty(&log).unwrap()),
+        Err(err) => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Discovers every WIT package directory under `roots` and lints them in
+/// parallel (up to `jobs` `wasm-tools` invocations at once), printing the
+/// combined SARIF 2.1.0 log. Unlike [`sarif`], a directory that fails to
+/// lint is skipped rather than aborting the whole run.
+async fn sarif_workspace(roots: Vec<PathBuf>, jobs: usize) {
+    let log = wit_lsp::emit_sarif_workspace(&roots, jobs).await;
+    println!("{}", serde_json::to_string_pretty(&log).unwrap());
+}
+
+/// Default number of concurrent `wasm-tools component wit` invocations for
+/// `sarif --workspace`, when `--jobs` isn't given.
+const DEFAULT_WORKSPACE_JOBS: usize = 4;
+
 #[tokio::main]
 async fn main() {
-    start().await;
+    let mut args = std::env::args().skip(1);
+
+    match args.next().as_deref() {
+        Some("sarif") => {
+            let mut workspace = false;
+            let mut jobs = DEFAULT_WORKSPACE_JOBS;
+            let mut dirs = Vec::new();
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--workspace" => workspace = true,
+                    "--jobs" => {
+                        jobs = args
+                            .next()
+                            .and_then(|value| value.parse().ok())
+                            .unwrap_or(DEFAULT_WORKSPACE_JOBS);
+                    }
+                    _ => dirs.push(PathBuf::from(arg)),
+                }
+            }
+
+            if workspace {
+                sarif_workspace(dirs, jobs).await;
+            } else {
+                sarif(dirs).await;
+            }
+        }
+        Some(other) => {
+            eprintln!("error: unrecognized argument `{other}`");
+            std::process::exit(1);
+        }
+        None => start().await,
+    }
 }
@@ -2,7 +2,7 @@ use wasm_bindgen::prelude::*;
 use std::collections::HashMap;
 use std::path::Path;
 
-use wit_parser::{Resolve, PackageId};
+use wit_parser::{Resolve, PackageId, WorldId, WorldItem, WorldKey, Type, TypeDefKind, TypeId};
 use anyhow::Context;
 // For component decoding (no text printing here; CLI fallback will be used)
 use wit_component as wcomp;
@@ -44,6 +44,697 @@ fn bytes_to_latin1_string(bytes: &[u8]) -> String {
     bytes.iter().map(|&b| b as char).collect()
 }
 
+/// Recovers the `(line, column, marker_length)` wit-parser points at from its
+/// error `Display` text, which renders in the same rustc-style
+/// `--> inline.wit:LINE:COL` plus caret-underlined snippet that `wasm-tools`
+/// itself uses. Both `line` and `column` are 1-based, matching the message.
+fn parse_error_span(message: &str) -> Option<(usize, usize, usize)> {
+    let after_arrow = message.split("-->").nth(1)?;
+    let location = after_arrow.lines().next()?.trim();
+    let mut parts = location.rsplitn(3, ':');
+    let column = parts.next()?.trim().parse::<usize>().ok()?;
+    let line = parts.next()?.trim().parse::<usize>().ok()?;
+
+    // The underline row is `   |     ^^^`: the caret run follows the `|`.
+    let marker_len = message
+        .lines()
+        .filter_map(|l| l.trim_start().strip_prefix('|'))
+        .map(str::trim_start)
+        .find(|l| l.starts_with('^'))
+        .map_or(1, |l| l.chars().take_while(|&c| c == '^' || c == '-').count().max(1));
+
+    Some((line, column, marker_len))
+}
+
+/// Converts a 1-based `(line, column)` pair into a 0-based byte offset into
+/// `content`, clamping to the end of the line if `column` overshoots it.
+fn line_col_to_offset(content: &str, line: usize, column: usize) -> usize {
+    let mut offset = 0;
+    for (index, text) in content.split('\n').enumerate() {
+        if index + 1 == line {
+            return offset + (column.saturating_sub(1)).min(text.len());
+        }
+        offset += text.len() + 1;
+    }
+    content.len()
+}
+
+/// Converts a 0-based byte offset into `content` to a 1-based `(line,
+/// column)` pair, the inverse of [`line_col_to_offset`].
+fn offset_to_line_col(content: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for (index, ch) in content.char_indices() {
+        if index >= offset {
+            break;
+        }
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// Builds a span object in the same shape `validateWitSyntaxDetailed` uses,
+/// so editor code can treat both as clickable locations uniformly.
+fn span_json(content: &str, start: usize, len: usize) -> serde_json::Value {
+    let (line, column) = offset_to_line_col(content, start);
+    let (end_line, end_column) = offset_to_line_col(content, start + len);
+    serde_json::json!({
+        "line": line,
+        "column": column,
+        "endLine": end_line,
+        "endColumn": end_column,
+        "offset": start,
+    })
+}
+
+/// Locates `name` inside the first occurrence of `context` (a short snippet
+/// of surrounding source, e.g. `"record foo"` or `"bar:"`) and returns its
+/// span. This is a best-effort lexical lookup -- `Resolve` itself discards
+/// spans once a package is merged -- so a name that's ambiguous within its
+/// own `context` snippet may point at the wrong occurrence; good enough for
+/// an outline view where being approximately right beats not being
+/// clickable at all.
+fn locate_span(content: &str, context: &str, name: &str) -> serde_json::Value {
+    match content.find(context).and_then(|start| {
+        context
+            .rfind(name)
+            .map(|name_offset| start + name_offset)
+    }) {
+        Some(start) => span_json(content, start, name.len()),
+        None => span_json(content, 0, 0),
+    }
+}
+
+/// Renders a WIT [`Type`] to the textual form it would appear as in source:
+/// built-in primitives by keyword, and named types by their declared name.
+/// Only the handful of anonymous compound shapes (`list`, `tuple`, `option`,
+/// `result`) are expanded inline, mirroring [`ts_type`]'s treatment of the
+/// same cases for the TypeScript side.
+fn wit_type(resolve: &Resolve, ty: &Type) -> String {
+    match ty {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::S8 => "s8".to_string(),
+        Type::S16 => "s16".to_string(),
+        Type::S32 => "s32".to_string(),
+        Type::S64 => "s64".to_string(),
+        Type::F32 => "f32".to_string(),
+        Type::F64 => "f64".to_string(),
+        Type::Char => "char".to_string(),
+        Type::String => "string".to_string(),
+        Type::Id(id) => wit_type_id(resolve, *id),
+    }
+}
+
+fn wit_type_id(resolve: &Resolve, id: TypeId) -> String {
+    let def = &resolve.types[id];
+    match &def.kind {
+        TypeDefKind::List(element) => format!("list<{}>", wit_type(resolve, element)),
+        TypeDefKind::Tuple(tuple) => {
+            let members: Vec<String> = tuple.types.iter().map(|t| wit_type(resolve, t)).collect();
+            format!("tuple<{}>", members.join(", "))
+        }
+        TypeDefKind::Option(inner) => format!("option<{}>", wit_type(resolve, inner)),
+        TypeDefKind::Result(result) => match (result.ok, result.err) {
+            (None, None) => "result".to_string(),
+            (Some(ok), None) => format!("result<{}>", wit_type(resolve, &ok)),
+            (None, Some(err)) => format!("result<_, {}>", wit_type(resolve, &err)),
+            (Some(ok), Some(err)) => {
+                format!("result<{}, {}>", wit_type(resolve, &ok), wit_type(resolve, &err))
+            }
+        },
+        TypeDefKind::Type(alias) => wit_type(resolve, alias),
+        _ => def.name.clone().unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+/// Renders a function's WIT signature, e.g. `name: func(a: u32) -> string`.
+fn wit_function_signature(resolve: &Resolve, name: &str, function: &wit_parser::Function) -> String {
+    let params: Vec<String> = function
+        .params
+        .iter()
+        .map(|(param_name, ty)| format!("{param_name}: {}", wit_type(resolve, ty)))
+        .collect();
+
+    let result = match &function.results {
+        wit_parser::Results::Named(named) if named.is_empty() => None,
+        wit_parser::Results::Named(named) if named.len() == 1 => {
+            Some(wit_type(resolve, &named[0].1))
+        }
+        wit_parser::Results::Named(named) => {
+            let fields: Vec<String> = named
+                .iter()
+                .map(|(n, ty)| format!("{n}: {}", wit_type(resolve, ty)))
+                .collect();
+            Some(format!("({})", fields.join(", ")))
+        }
+        wit_parser::Results::Anon(ty) => Some(wit_type(resolve, ty)),
+    };
+
+    match result {
+        Some(result) => format!("{name}: func({}) -> {result}", params.join(", ")),
+        None => format!("{name}: func({})", params.join(", ")),
+    }
+}
+
+/// Locates the span of a top-level declaration (`interface foo`, `record
+/// foo`, `world foo`, ...) by its keyword and name.
+fn decl_span(content: &str, keyword: &str, name: &str) -> serde_json::Value {
+    locate_span(content, &format!("{keyword} {name}"), name)
+}
+
+/// Locates the span of a `name: ...` member (a function or record field),
+/// which is how both are written in WIT source.
+fn member_span(content: &str, name: &str) -> serde_json::Value {
+    locate_span(content, &format!("{name}:"), name)
+}
+
+/// Locates the span of a bare case name (`flags`/`variant`/`enum`), which
+/// has no distinguishing punctuation of its own; this falls back to the
+/// first occurrence of `name` anywhere in the document.
+fn case_span(content: &str, name: &str) -> serde_json::Value {
+    locate_span(content, name, name)
+}
+
+/// Builds the JSON outline entry for a single named type definition,
+/// including its kind-specific members (fields, cases, flags, or resource
+/// methods) and each member's span. `methods` holds any functions whose
+/// `FunctionKind` targets this type, keyed by its [`TypeId`], gathered by
+/// the caller since only the enclosing interface/world knows its functions.
+fn type_symbol(
+    resolve: &Resolve,
+    content: &str,
+    name: &str,
+    id: TypeId,
+    methods: &[(&String, &wit_parser::Function)],
+) -> serde_json::Value {
+    let def = &resolve.types[id];
+    match &def.kind {
+        TypeDefKind::Record(record) => serde_json::json!({
+            "name": name,
+            "kind": "record",
+            "span": decl_span(content, "record", name),
+            "fields": record.fields.iter().map(|f| serde_json::json!({
+                "name": f.name,
+                "type": wit_type(resolve, &f.ty),
+                "span": member_span(content, &f.name),
+            })).collect::<Vec<_>>(),
+        }),
+        TypeDefKind::Variant(variant) => serde_json::json!({
+            "name": name,
+            "kind": "variant",
+            "span": decl_span(content, "variant", name),
+            "cases": variant.cases.iter().map(|c| serde_json::json!({
+                "name": c.name,
+                "type": c.ty.as_ref().map(|ty| wit_type(resolve, ty)),
+                "span": case_span(content, &c.name),
+            })).collect::<Vec<_>>(),
+        }),
+        TypeDefKind::Enum(enum_) => serde_json::json!({
+            "name": name,
+            "kind": "enum",
+            "span": decl_span(content, "enum", name),
+            "cases": enum_.cases.iter().map(|c| serde_json::json!({
+                "name": c.name,
+                "span": case_span(content, &c.name),
+            })).collect::<Vec<_>>(),
+        }),
+        TypeDefKind::Flags(flags) => serde_json::json!({
+            "name": name,
+            "kind": "flags",
+            "span": decl_span(content, "flags", name),
+            "flags": flags.flags.iter().map(|f| serde_json::json!({
+                "name": f.name,
+                "span": case_span(content, &f.name),
+            })).collect::<Vec<_>>(),
+        }),
+        TypeDefKind::Resource => serde_json::json!({
+            "name": name,
+            "kind": "resource",
+            "span": decl_span(content, "resource", name),
+            "methods": methods.iter().map(|(_, function)| serde_json::json!({
+                "name": function.item_name(),
+                "signature": wit_function_signature(resolve, function.item_name(), function),
+                "span": member_span(content, function.item_name()),
+            })).collect::<Vec<_>>(),
+        }),
+        TypeDefKind::Type(alias) => serde_json::json!({
+            "name": name,
+            "kind": "type",
+            "span": decl_span(content, "type", name),
+            "aliasOf": wit_type(resolve, alias),
+        }),
+        _ => serde_json::json!({
+            "name": name,
+            "kind": "unknown",
+            "span": decl_span(content, name, name),
+        }),
+    }
+}
+
+/// Groups `functions` by the [`TypeId`] their [`wit_parser::FunctionKind`]
+/// targets (method/static/constructor), for attaching to their owning
+/// resource's `methods` list. Freestanding functions map to no key and are
+/// returned separately as the interface/world's own function list.
+fn partition_methods<'a>(
+    functions: impl Iterator<Item = (&'a String, &'a wit_parser::Function)>,
+) -> (
+    Vec<(&'a String, &'a wit_parser::Function)>,
+    HashMap<TypeId, Vec<(&'a String, &'a wit_parser::Function)>>,
+) {
+    let mut freestanding = Vec::new();
+    let mut by_resource: HashMap<TypeId, Vec<(&'a String, &'a wit_parser::Function)>> =
+        HashMap::new();
+
+    for (name, function) in functions {
+        match function.kind {
+            wit_parser::FunctionKind::Freestanding => freestanding.push((name, function)),
+            wit_parser::FunctionKind::Method(id)
+            | wit_parser::FunctionKind::Static(id)
+            | wit_parser::FunctionKind::Constructor(id) => {
+                by_resource.entry(id).or_default().push((name, function));
+            }
+        }
+    }
+
+    (freestanding, by_resource)
+}
+
+/// Builds the JSON outline entry for an `interface`: its own span plus its
+/// named type definitions and freestanding functions, each with a span.
+fn interface_symbol(
+    resolve: &Resolve,
+    content: &str,
+    name: &str,
+    id: wit_parser::InterfaceId,
+) -> serde_json::Value {
+    let interface = &resolve.interfaces[id];
+    let (functions, methods_by_resource) = partition_methods(interface.functions.iter());
+
+    let types: Vec<serde_json::Value> = interface
+        .types
+        .iter()
+        .map(|(type_name, &type_id)| {
+            let methods = methods_by_resource
+                .get(&type_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            type_symbol(resolve, content, type_name, type_id, methods)
+        })
+        .collect();
+
+    let functions: Vec<serde_json::Value> = functions
+        .iter()
+        .map(|(fn_name, function)| {
+            serde_json::json!({
+                "name": fn_name,
+                "signature": wit_function_signature(resolve, fn_name, function),
+                "span": member_span(content, fn_name),
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "name": name,
+        "span": decl_span(content, "interface", name),
+        "types": types,
+        "functions": functions,
+    })
+}
+
+/// Builds the JSON outline entry for a `world`: its own span plus its
+/// imports and exports, each named by the interface, function, or type it
+/// refers to.
+fn world_symbol(resolve: &Resolve, content: &str, name: &str, id: WorldId) -> serde_json::Value {
+    let world = &resolve.worlds[id];
+
+    let world_item_json = |direction: &str, key: &WorldKey, item: &WorldItem| {
+        let (name, kind) = match item {
+            WorldItem::Interface { .. } => {
+                let name = match key {
+                    WorldKey::Name(name) => name.clone(),
+                    WorldKey::Interface(id) => resolve.interfaces[*id]
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| "interface".to_string()),
+                };
+                (name, "interface")
+            }
+            WorldItem::Function(function) => (function.name.clone(), "function"),
+            WorldItem::Type(type_id) => {
+                let name = resolve.types[*type_id]
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| "type".to_string());
+                (name, "type")
+            }
+        };
+        serde_json::json!({
+            "name": name,
+            "kind": kind,
+            "span": locate_span(content, &format!("{direction} {name}"), &name),
+        })
+    };
+
+    let imports: Vec<serde_json::Value> = world
+        .imports
+        .iter()
+        .map(|(key, item)| world_item_json("import", key, item))
+        .collect();
+    let exports: Vec<serde_json::Value> = world
+        .exports
+        .iter()
+        .map(|(key, item)| world_item_json("export", key, item))
+        .collect();
+
+    serde_json::json!({
+        "name": name,
+        "span": decl_span(content, "world", name),
+        "imports": imports,
+        "exports": exports,
+    })
+}
+
+/// Builds the `diagnostics` array entry for a single parse error, preferring
+/// the span wit-parser's message points at and falling back to the whole
+/// document when no span can be recovered.
+fn diagnostic_from_error(content: &str, message: &str, error_type: &str) -> serde_json::Value {
+    let (line, column, end_line, end_column, offset) = match parse_error_span(message) {
+        Some((line, column, marker_len)) => (
+            line,
+            column,
+            line,
+            column + marker_len,
+            line_col_to_offset(content, line, column),
+        ),
+        None => {
+            let last_line = content.split('\n').count().max(1);
+            let last_column = content.split('\n').next_back().map_or(1, |l| l.len() + 1);
+            (1, 1, last_line, last_column, 0)
+        }
+    };
+
+    serde_json::json!({
+        "message": message,
+        "severity": "error",
+        "errorType": error_type,
+        "line": line,
+        "column": column,
+        "endLine": end_line,
+        "endColumn": end_column,
+        "offset": offset,
+    })
+}
+
+/// JSON-deserializable override for [`rust::Opts`], covering the knobs
+/// extension users most often need without hand-editing generated output:
+/// the ownership model, whether every type in the world is generated (not
+/// just ones reachable from exported functions), and runtime/async path
+/// overrides. Unknown keys are rejected so a typo in `options` surfaces as
+/// an `error.txt` instead of being silently ignored.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct RustOptions {
+    generate_all: Option<bool>,
+    ownership: Option<RustOwnership>,
+    runtime_path: Option<String>,
+    bitflags_path: Option<String>,
+    async_: Option<bool>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum RustOwnership {
+    Owning,
+    Borrowing,
+}
+
+impl RustOptions {
+    fn apply(self, opts: &mut rust::Opts) {
+        if let Some(generate_all) = self.generate_all {
+            opts.generate_all = generate_all;
+        }
+        if let Some(ownership) = self.ownership {
+            opts.ownership = match ownership {
+                RustOwnership::Owning => rust::Ownership::Owning,
+                RustOwnership::Borrowing => rust::Ownership::Borrowing {
+                    duplicate_if_necessary: false,
+                },
+            };
+        }
+        if let Some(runtime_path) = self.runtime_path {
+            opts.runtime_path = Some(runtime_path);
+        }
+        if let Some(bitflags_path) = self.bitflags_path {
+            opts.bitflags_path = Some(bitflags_path);
+        }
+        if let Some(async_) = self.async_ {
+            opts.async_ = if async_ {
+                rust::AsyncConfig::All
+            } else {
+                rust::AsyncConfig::None
+            };
+        }
+    }
+}
+
+/// JSON-deserializable override for [`c::Opts`].
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct COptions {
+    /// Run `clang-format` over the generated `.c`/`.h` files.
+    format: Option<bool>,
+}
+
+impl COptions {
+    fn apply(self, opts: &mut c::Opts) {
+        if let Some(format) = self.format {
+            opts.format = format;
+        }
+    }
+}
+
+/// JSON-deserializable override for [`cpp::Opts`].
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct CppOptions {
+    /// Run `clang-format` over the generated `.cpp`/`.h` files.
+    format: Option<bool>,
+}
+
+impl CppOptions {
+    fn apply(self, opts: &mut cpp::Opts) {
+        if let Some(format) = self.format {
+            opts.format = format;
+        }
+    }
+}
+
+/// JSON-deserializable override for [`csharp::Opts`].
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct CSharpOptions {
+    /// Emit a stub class implementing the world's exports, for users who
+    /// want a starting point rather than hand-writing the implementation.
+    generate_stub: Option<bool>,
+}
+
+impl CSharpOptions {
+    fn apply(self, opts: &mut csharp::Opts) {
+        if let Some(generate_stub) = self.generate_stub {
+            opts.generate_stub = generate_stub;
+        }
+    }
+}
+
+/// Maps a WIT [`Type`] to the TypeScript type it should appear as in a
+/// generated `.d.ts`. Named type definitions are referenced by their WIT
+/// name (PascalCase is assumed to already match, since `.d.ts` output isn't
+/// run through any casing convention beyond what wit-parser already gives
+/// us); only the handful of built-in shapes (`list`, `tuple`, `option`,
+/// `result`) are expanded inline.
+fn ts_type(resolve: &Resolve, ty: &Type) -> String {
+    match ty {
+        Type::Bool => "boolean".to_string(),
+        Type::U8 | Type::U16 | Type::U32 | Type::S8 | Type::S16 | Type::S32 => {
+            "number".to_string()
+        }
+        Type::U64 | Type::S64 => "bigint".to_string(),
+        Type::F32 | Type::F64 => "number".to_string(),
+        Type::Char | Type::String => "string".to_string(),
+        Type::Id(id) => ts_type_id(resolve, *id),
+    }
+}
+
+fn ts_type_id(resolve: &Resolve, id: TypeId) -> String {
+    let def = &resolve.types[id];
+    match &def.kind {
+        TypeDefKind::List(Type::U8) => "Uint8Array".to_string(),
+        TypeDefKind::List(element) => format!("{}[]", ts_type(resolve, element)),
+        TypeDefKind::Tuple(tuple) => {
+            let members: Vec<String> = tuple.types.iter().map(|t| ts_type(resolve, t)).collect();
+            format!("[{}]", members.join(", "))
+        }
+        TypeDefKind::Option(inner) => format!("{} | undefined", ts_type(resolve, inner)),
+        TypeDefKind::Result(result) => {
+            let ok = result.ok.map_or("undefined".to_string(), |t| ts_type(resolve, &t));
+            let err = result.err.map_or("undefined".to_string(), |t| ts_type(resolve, &t));
+            format!(
+                "{{ tag: 'ok', val: {ok} }} | {{ tag: 'err', val: {err} }}"
+            )
+        }
+        TypeDefKind::Type(alias) => ts_type(resolve, alias),
+        _ => def
+            .name
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+/// Renders the declaration for a named type definition: an `interface` for
+/// `record`, a tagged union for `variant`, a string-literal union for `enum`,
+/// an `interface` of `boolean` fields for `flags`, and a plain `type` alias for
+/// everything else (including resources, which are opaque handles from the
+/// host's perspective).
+fn ts_typedef(resolve: &Resolve, id: TypeId) -> Option<String> {
+    let def = &resolve.types[id];
+    let name = def.name.as_deref()?;
+
+    let body = match &def.kind {
+        TypeDefKind::Record(record) => {
+            let fields: Vec<String> = record
+                .fields
+                .iter()
+                .map(|f| format!("  {}: {};", f.name, ts_type(resolve, &f.ty)))
+                .collect();
+            format!("export interface {name} {{\n{}\n}}", fields.join("\n"))
+        }
+        TypeDefKind::Variant(variant) => {
+            let cases: Vec<String> = variant
+                .cases
+                .iter()
+                .map(|c| match &c.ty {
+                    Some(ty) => format!(
+                        "{{ tag: '{}', val: {} }}",
+                        c.name,
+                        ts_type(resolve, ty)
+                    ),
+                    None => format!("{{ tag: '{}' }}", c.name),
+                })
+                .collect();
+            format!("export type {name} =\n  | {};", cases.join("\n  | "))
+        }
+        TypeDefKind::Enum(enum_) => {
+            let cases: Vec<String> = enum_
+                .cases
+                .iter()
+                .map(|c| format!("'{}'", c.name))
+                .collect();
+            format!("export type {name} = {};", cases.join(" | "))
+        }
+        TypeDefKind::Flags(flags) => {
+            let fields: Vec<String> = flags
+                .flags
+                .iter()
+                .map(|f| format!("  {}: boolean;", f.name))
+                .collect();
+            format!("export interface {name} {{\n{}\n}}", fields.join("\n"))
+        }
+        TypeDefKind::Resource => format!("export type {name} = number;"),
+        _ => format!("export type {name} = {};", ts_type_id(resolve, id)),
+    };
+
+    Some(body)
+}
+
+/// Renders a function's TypeScript signature: `name(params): ReturnType;`.
+/// Multiple named results (rare; most functions return zero or one value)
+/// are rendered as an object type keyed by result name.
+fn ts_function(resolve: &Resolve, name: &str, function: &wit_parser::Function) -> String {
+    let params: Vec<String> = function
+        .params
+        .iter()
+        .map(|(param_name, ty)| format!("{param_name}: {}", ts_type(resolve, ty)))
+        .collect();
+
+    let result = match &function.results {
+        wit_parser::Results::Named(named) if named.is_empty() => "void".to_string(),
+        wit_parser::Results::Named(named) if named.len() == 1 => ts_type(resolve, &named[0].1),
+        wit_parser::Results::Named(named) => {
+            let fields: Vec<String> = named
+                .iter()
+                .map(|(n, ty)| format!("{n}: {}", ts_type(resolve, ty)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        wit_parser::Results::Anon(ty) => ts_type(resolve, ty),
+    };
+
+    format!("export function {name}({}): {result};", params.join(", "))
+}
+
+/// Renders every named type and function belonging to an interface, in the
+/// order wit-parser records them.
+fn ts_interface_body(resolve: &Resolve, interface_id: wit_parser::InterfaceId) -> String {
+    let interface = &resolve.interfaces[interface_id];
+    let mut lines = Vec::new();
+
+    for &type_id in interface.types.values() {
+        if let Some(decl) = ts_typedef(resolve, type_id) {
+            lines.push(decl);
+        }
+    }
+    for (name, function) in &interface.functions {
+        lines.push(ts_function(resolve, name, function));
+    }
+
+    lines.join("\n\n")
+}
+
+/// Renders the full `.d.ts` body for `world`: every imported and exported
+/// interface's types and functions, plus any bare functions or type aliases
+/// declared directly on the world.
+fn ts_world_body(resolve: &Resolve, world_id: WorldId) -> String {
+    let world = &resolve.worlds[world_id];
+    let mut sections = Vec::new();
+
+    for (key, item) in world.imports.iter().chain(world.exports.iter()) {
+        match item {
+            WorldItem::Interface { id, .. } => {
+                let heading = match key {
+                    WorldKey::Name(name) => name.clone(),
+                    WorldKey::Interface(id) => resolve.interfaces[*id]
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| "interface".to_string()),
+                };
+                sections.push(format!(
+                    "// --- {heading} ---\n{}",
+                    ts_interface_body(resolve, *id)
+                ));
+            }
+            WorldItem::Function(function) => {
+                sections.push(ts_function(resolve, &function.name, function));
+            }
+            WorldItem::Type(type_id) => {
+                if let Some(decl) = ts_typedef(resolve, *type_id) {
+                    sections.push(decl);
+                }
+            }
+        }
+    }
+
+    sections.join("\n\n")
+}
+
 /// A WIT validation and processing instance
 #[wasm_bindgen]
 #[derive(Default)]
@@ -213,6 +904,44 @@ impl WitBindgen {
         }
     }
 
+    /// Produce a structured symbol outline of a WIT document: its
+    /// interfaces (with their types and functions) and worlds (with their
+    /// imports and exports), for a document-outline / go-to-symbol
+    /// provider. Each symbol is annotated with a source span, recovered
+    /// lexically since `Resolve` discards spans once a package is merged
+    /// (see [`locate_span`]).
+    /// Returns JSON string: `{ "interfaces": [...], "worlds": [...] }`, or
+    /// `{ "interfaces": [], "worlds": [] }` if `content` fails to parse.
+    #[wasm_bindgen(js_name = extractSymbols)]
+    pub fn extract_symbols(&self, content: &str) -> String {
+        let empty = || serde_json::json!({ "interfaces": [], "worlds": [] });
+
+        let inline_path = Path::new("inline.wit");
+        let mut resolve = Resolve::default();
+        let result = match resolve.push_str(inline_path, content) {
+            Ok(package_id) => {
+                let package = &resolve.packages[package_id];
+                let interfaces: Vec<serde_json::Value> = package
+                    .interfaces
+                    .iter()
+                    .map(|(name, &id)| interface_symbol(&resolve, content, name, id))
+                    .collect();
+                let worlds: Vec<serde_json::Value> = package
+                    .worlds
+                    .iter()
+                    .map(|(name, &id)| world_symbol(&resolve, content, name, id))
+                    .collect();
+                serde_json::json!({ "interfaces": interfaces, "worlds": worlds })
+            }
+            Err(e) => {
+                console_error(&format!("Symbol extraction failed: {}", e));
+                empty()
+            }
+        };
+
+        result.to_string()
+    }
+
     /// Get version information
     #[wasm_bindgen]
     pub fn version(&self) -> String {
@@ -221,32 +950,49 @@ impl WitBindgen {
 
     /// Generate language bindings from WIT content
     /// Supports: rust, c, csharp, go, moonbit
+    /// as well as cpp (`c++`) and js (`javascript`, `typescript`).
+    /// `options`, when present, is a JSON object of generator-specific
+    /// overrides (see the `*Options` structs below); unknown keys are
+    /// rejected so a typo surfaces as an `error.txt` instead of being
+    /// silently ignored.
     /// Returns JSON string containing file map
     #[wasm_bindgen(js_name = generateBindings)]
-    pub fn generate_bindings(&self, content: &str, language: &str, world_name: Option<String>) -> String {
+    pub fn generate_bindings(&self, content: &str, language: &str, world_name: Option<String>, options: Option<String>) -> String {
         let files = match language.to_lowercase().as_str() {
-            "rust" => self.generate_rust_bindings(content, world_name),
-            "c" => self.generate_c_bindings(content, world_name),
-            "cpp" | "c++" => self.generate_cpp_bindings(content, world_name),
-            "csharp" | "c#" => self.generate_csharp_bindings(content, world_name),
+            "rust" => self.generate_rust_bindings(content, world_name, options),
+            "c" => self.generate_c_bindings(content, world_name, options),
+            "cpp" | "c++" => self.generate_cpp_bindings(content, world_name, options),
+            "csharp" | "c#" => self.generate_csharp_bindings(content, world_name, options),
             "go" => self.generate_go_bindings(content, world_name),
             "moonbit" => self.generate_moonbit_bindings(content, world_name),
+            "js" | "javascript" | "typescript" => self.generate_js_bindings(content, world_name),
             _ => {
                 let mut error_files = HashMap::new();
                 error_files.insert(
-                    "error.txt".to_string(), 
-                    format!("// Unsupported language: {}\n// Supported languages: rust, c, cpp, csharp, go, moonbit", language)
+                    "error.txt".to_string(),
+                    format!("// Unsupported language: {}\n// Supported languages: rust, c, cpp, csharp, go, moonbit, js", language)
                 );
                 error_files
             },
         };
-        
+
         serde_json::to_string(&files).unwrap_or_else(|_| "{}".to_string())
     }
 
+    /// Parses `raw` (a JSON object, if present) into a generator's options
+    /// type, rejecting unknown fields so a misconfigured `options` value
+    /// surfaces as a clear error rather than being ignored.
+    fn parse_options<T: serde::de::DeserializeOwned + Default>(raw: Option<String>) -> Result<T, anyhow::Error> {
+        match raw {
+            Some(raw) => serde_json::from_str(&raw)
+                .with_context(|| format!("Invalid options: {raw}")),
+            None => Ok(T::default()),
+        }
+    }
+
     /// Generate C bindings using wit-bindgen-c library
-    fn generate_c_bindings(&self, content: &str, world_name: Option<String>) -> HashMap<String, String> {
-        match self.generate_c_with_wit_bindgen(content, world_name.as_deref()) {
+    fn generate_c_bindings(&self, content: &str, world_name: Option<String>, options: Option<String>) -> HashMap<String, String> {
+        match self.generate_c_with_wit_bindgen(content, world_name.as_deref(), options) {
             Ok(files) => files,
             Err(e) => {
                 console_error(&format!("wit-bindgen-c failed: {}", e));
@@ -259,21 +1005,23 @@ impl WitBindgen {
             }
         }
     }
-    
+
     /// Generate C bindings using wit-bindgen-c library
-    fn generate_c_with_wit_bindgen(&self, content: &str, world_name: Option<&str>) -> Result<HashMap<String, String>, anyhow::Error> {
+    fn generate_c_with_wit_bindgen(&self, content: &str, world_name: Option<&str>, options: Option<String>) -> Result<HashMap<String, String>, anyhow::Error> {
         let inline_path = Path::new("inline.wit");
         let mut resolve = Resolve::default();
         let package_id = resolve.push_str(inline_path, content)
             .with_context(|| "Failed to parse WIT content for C binding generation")?;
-        
+
         let world_id = if let Some(world_name) = world_name {
             resolve.select_world(&[package_id], Some(world_name))?
         } else {
             resolve.select_world(&[package_id], None)?
         };
-        
-        let opts = c::Opts::default();
+
+        let overrides: COptions = Self::parse_options(options)?;
+        let mut opts = c::Opts::default();
+        overrides.apply(&mut opts);
         let mut generator = opts.build();
         let mut files = Files::default();
         
@@ -288,8 +1036,8 @@ impl WitBindgen {
     }
     
     /// Generate C++ bindings using wit-bindgen-cpp library
-    fn generate_cpp_bindings(&self, content: &str, world_name: Option<String>) -> HashMap<String, String> {
-        match self.generate_cpp_with_wit_bindgen(content, world_name.as_deref()) {
+    fn generate_cpp_bindings(&self, content: &str, world_name: Option<String>, options: Option<String>) -> HashMap<String, String> {
+        match self.generate_cpp_with_wit_bindgen(content, world_name.as_deref(), options) {
             Ok(files) => files,
             Err(e) => {
                 console_error(&format!("wit-bindgen-cpp failed: {}", e));
@@ -302,21 +1050,23 @@ impl WitBindgen {
             }
         }
     }
-    
+
     /// Generate C++ bindings using wit-bindgen-cpp library
-    fn generate_cpp_with_wit_bindgen(&self, content: &str, world_name: Option<&str>) -> Result<HashMap<String, String>, anyhow::Error> {
+    fn generate_cpp_with_wit_bindgen(&self, content: &str, world_name: Option<&str>, options: Option<String>) -> Result<HashMap<String, String>, anyhow::Error> {
         let inline_path = Path::new("inline.wit");
         let mut resolve = Resolve::default();
         let package_id = resolve.push_str(inline_path, content)
             .with_context(|| "Failed to parse WIT content for C++ binding generation")?;
-        
+
         let world_id = if let Some(world_name) = world_name {
             resolve.select_world(&[package_id], Some(world_name))?
         } else {
             resolve.select_world(&[package_id], None)?
         };
-        
-        let opts = cpp::Opts::default();
+
+        let overrides: CppOptions = Self::parse_options(options)?;
+        let mut opts = cpp::Opts::default();
+        overrides.apply(&mut opts);
         let mut generator = opts.build(None);
         let mut files = Files::default();
         
@@ -331,8 +1081,8 @@ impl WitBindgen {
     }
     
     /// Generate Rust bindings using wit-bindgen-rust library
-    fn generate_rust_bindings(&self, content: &str, world_name: Option<String>) -> HashMap<String, String> {
-        match self.generate_rust_with_wit_bindgen(content, world_name.as_deref()) {
+    fn generate_rust_bindings(&self, content: &str, world_name: Option<String>, options: Option<String>) -> HashMap<String, String> {
+        match self.generate_rust_with_wit_bindgen(content, world_name.as_deref(), options) {
             Ok(files) => files,
             Err(e) => {
                 console_error(&format!("wit-bindgen-rust failed: {}", e));
@@ -347,22 +1097,24 @@ impl WitBindgen {
     }
 
     /// Generate Rust bindings using wit-bindgen-rust library
-    fn generate_rust_with_wit_bindgen(&self, content: &str, world_name: Option<&str>) -> Result<HashMap<String, String>, anyhow::Error> {
+    fn generate_rust_with_wit_bindgen(&self, content: &str, world_name: Option<&str>, options: Option<String>) -> Result<HashMap<String, String>, anyhow::Error> {
         let inline_path = Path::new("inline.wit");
         let mut resolve = Resolve::default();
         let package_id = resolve.push_str(inline_path, content)
             .with_context(|| "Failed to parse WIT content for Rust binding generation")?;
-        
+
         let world_id = if let Some(world_name) = world_name {
             resolve.select_world(&[package_id], Some(world_name))?
         } else {
             resolve.select_world(&[package_id], None)?
         };
-        
-        let opts = rust::Opts { 
-            generate_all: true, 
-            ..Default::default() 
+
+        let overrides: RustOptions = Self::parse_options(options)?;
+        let mut opts = rust::Opts {
+            generate_all: true,
+            ..Default::default()
         };
+        overrides.apply(&mut opts);
         let mut generator = opts.build();
         let mut files = Files::default();
         
@@ -377,8 +1129,8 @@ impl WitBindgen {
     }
 
     /// Generate C# bindings using wit-bindgen-csharp library
-    fn generate_csharp_bindings(&self, content: &str, world_name: Option<String>) -> HashMap<String, String> {
-        match self.generate_csharp_with_wit_bindgen(content, world_name.as_deref()) {
+    fn generate_csharp_bindings(&self, content: &str, world_name: Option<String>, options: Option<String>) -> HashMap<String, String> {
+        match self.generate_csharp_with_wit_bindgen(content, world_name.as_deref(), options) {
             Ok(files) => files,
             Err(e) => {
                 console_error(&format!("wit-bindgen-csharp failed: {}", e));
@@ -393,19 +1145,21 @@ impl WitBindgen {
     }
 
     /// Generate C# bindings using wit-bindgen-csharp library
-    fn generate_csharp_with_wit_bindgen(&self, content: &str, world_name: Option<&str>) -> Result<HashMap<String, String>, anyhow::Error> {
+    fn generate_csharp_with_wit_bindgen(&self, content: &str, world_name: Option<&str>, options: Option<String>) -> Result<HashMap<String, String>, anyhow::Error> {
         let inline_path = Path::new("inline.wit");
         let mut resolve = Resolve::default();
         let package_id = resolve.push_str(inline_path, content)
             .with_context(|| "Failed to parse WIT content for C# binding generation")?;
-        
+
         let world_id = if let Some(world_name) = world_name {
             resolve.select_world(&[package_id], Some(world_name))?
         } else {
             resolve.select_world(&[package_id], None)?
         };
-        
-        let opts = csharp::Opts::default();
+
+        let overrides: CSharpOptions = Self::parse_options(options)?;
+        let mut opts = csharp::Opts::default();
+        overrides.apply(&mut opts);
         let mut generator = opts.build();
         let mut files = Files::default();
         
@@ -483,6 +1237,154 @@ impl WitBindgen {
         Ok(result)
     }
 
+    /// Generate JS/TS bindings using js-component-bindgen (the engine behind
+    /// jco's transpile)
+    fn generate_js_bindings(&self, content: &str, world_name: Option<String>) -> HashMap<String, String> {
+        match self.generate_js_with_wit_bindgen(content, world_name.as_deref()) {
+            Ok(files) => files,
+            Err(e) => {
+                console_error(&format!("js-component-bindgen failed: {}", e));
+                let mut error_files = HashMap::new();
+                error_files.insert(
+                    "error.txt".to_string(),
+                    format!("JS binding generation failed: {}", e)
+                );
+                error_files
+            }
+        }
+    }
+
+    /// Generate JS/TS bindings using js-component-bindgen (the engine behind
+    /// jco's transpile). js-component-bindgen transpiles an encoded
+    /// component rather than raw WIT, so the world is first wrapped in a
+    /// dummy component whose core module only traps on every export - only
+    /// the component's types are used downstream, since the generated `.js`
+    /// glue replaces the dummy body entirely.
+    fn generate_js_with_wit_bindgen(&self, content: &str, world_name: Option<&str>) -> Result<HashMap<String, String>, anyhow::Error> {
+        let inline_path = Path::new("inline.wit");
+        let mut resolve = Resolve::default();
+        let package_id = resolve.push_str(inline_path, content)
+            .with_context(|| "Failed to parse WIT content for JS binding generation")?;
+
+        let world_id = if let Some(world_name) = world_name {
+            resolve.select_world(&[package_id], Some(world_name))?
+        } else {
+            resolve.select_world(&[package_id], None)?
+        };
+
+        let component = Self::encode_dummy_component(&resolve, world_id)
+            .with_context(|| "Failed to encode a dummy component for JS binding generation")?;
+
+        let transpiled = js_component_bindgen::transpile(
+            &component,
+            js_component_bindgen::TranspileOpts {
+                name: resolve.worlds[world_id].name.clone(),
+                ..Default::default()
+            },
+        )
+        .map_err(|e| anyhow::anyhow!("js-component-bindgen transpile failed: {e}"))?;
+
+        let mut result = HashMap::new();
+        for (filename, contents) in transpiled.files {
+            result.insert(filename, bytes_to_latin1_string(&contents));
+        }
+
+        Ok(result)
+    }
+
+    /// Builds a core module that declares (but never calls) every import and
+    /// export of `world`, embeds the component-type metadata `wit-component`
+    /// needs to recover those signatures, and encodes the pair into a
+    /// component binary.
+    fn encode_dummy_component(resolve: &Resolve, world_id: WorldId) -> anyhow::Result<Vec<u8>> {
+        let module = wcomp::dummy_module(resolve, world_id);
+        Self::embed_and_encode(resolve, world_id, module)
+    }
+
+    /// Embeds `world`'s component-type metadata into `module` and encodes
+    /// the pair into a component binary, validating the result so a broken
+    /// core module surfaces a meaningful error rather than an unusable
+    /// artifact.
+    fn embed_and_encode(resolve: &Resolve, world_id: WorldId, mut module: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        wcomp::embed_component_metadata(&mut module, resolve, world_id, wcomp::StringEncoding::UTF8)?;
+
+        wcomp::ComponentEncoder::default()
+            .module(&module)?
+            .validate(true)
+            .encode()
+    }
+
+    /// Wrap a core WebAssembly module back into a component using the WIT
+    /// world it implements. Returns an empty string on error.
+    #[wasm_bindgen(js_name = encodeComponent)]
+    pub fn encode_component(&self, core_wasm_bytes: &[u8], wit_content: &str, world_name: Option<String>) -> String {
+        match Self::encode_component_impl(core_wasm_bytes, wit_content, world_name.as_deref()) {
+            Ok(bytes) => bytes_to_latin1_string(&bytes),
+            Err(e) => {
+                console_error(&format!("Component encoding failed: {}", e));
+                String::new()
+            }
+        }
+    }
+
+    fn encode_component_impl(core_wasm_bytes: &[u8], wit_content: &str, world_name: Option<&str>) -> anyhow::Result<Vec<u8>> {
+        let inline_path = Path::new("inline.wit");
+        let mut resolve = Resolve::default();
+        let package_id = resolve.push_str(inline_path, wit_content)
+            .with_context(|| "Failed to parse WIT content for component encoding")?;
+
+        let world_id = if let Some(world_name) = world_name {
+            resolve.select_world(&[package_id], Some(world_name))?
+        } else {
+            resolve.select_world(&[package_id], None)?
+        };
+
+        Self::embed_and_encode(&resolve, world_id, core_wasm_bytes.to_vec())
+            .with_context(|| "Failed to encode component")
+    }
+
+    /// Generate standalone TypeScript declarations for a world: its imports
+    /// and exports, and the records/variants/enums/flags/resources they
+    /// reference, with no JS glue. Returns JSON string containing file map.
+    #[wasm_bindgen(js_name = generateTypeScriptTypes)]
+    pub fn generate_type_script_types(&self, content: &str, world_name: Option<String>) -> String {
+        let files = match self.generate_typescript_types_impl(content, world_name.as_deref()) {
+            Ok(files) => files,
+            Err(e) => {
+                console_error(&format!("TypeScript type generation failed: {}", e));
+                let mut error_files = HashMap::new();
+                error_files.insert(
+                    "error.txt".to_string(),
+                    format!("TypeScript type generation failed: {}", e)
+                );
+                error_files
+            }
+        };
+
+        serde_json::to_string(&files).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Generate standalone TypeScript declarations for a world
+    fn generate_typescript_types_impl(&self, content: &str, world_name: Option<&str>) -> Result<HashMap<String, String>, anyhow::Error> {
+        let inline_path = Path::new("inline.wit");
+        let mut resolve = Resolve::default();
+        let package_id = resolve.push_str(inline_path, content)
+            .with_context(|| "Failed to parse WIT content for TypeScript type generation")?;
+
+        let world_id = if let Some(world_name) = world_name {
+            resolve.select_world(&[package_id], Some(world_name))?
+        } else {
+            resolve.select_world(&[package_id], None)?
+        };
+
+        let world_name = &resolve.worlds[world_id].name;
+        let body = ts_world_body(&resolve, world_id);
+
+        let mut result = HashMap::new();
+        result.insert(format!("{world_name}.d.ts"), body);
+        Ok(result)
+    }
+
     /// Validate WIT syntax and return detailed error information
     #[wasm_bindgen(js_name = validateWitSyntaxDetailed)]
     pub fn validate_wit_syntax_detailed(&self, content: &str) -> String {
@@ -492,7 +1394,12 @@ impl WitBindgen {
             return serde_json::json!({
                 "valid": false,
                 "error": "Empty or whitespace-only content is not valid WIT",
-                "errorType": "validation"
+                "errorType": "validation",
+                "diagnostics": [diagnostic_from_error(
+                    content,
+                    "Empty or whitespace-only content is not valid WIT",
+                    "validation",
+                )],
             }).to_string();
         }
 
@@ -506,25 +1413,29 @@ impl WitBindgen {
             }
             Err(e) => {
                 let error_message = e.to_string();
-                
+
                 #[cfg(feature = "console_error_panic_hook")]
                 console_error(&format!("WIT parsing failed: {}", error_message));
-                
-                if error_message.contains("package not found") || 
+
+                let error_type = if error_message.contains("package not found") ||
                    error_message.contains("interface not found") ||
                    error_message.contains("failed to find package") {
-                        serde_json::json!({
-                            "valid": false,
-                            "error": format!("Dependency error: {}", error_message),
-                            "errorType": "dependency"
-                        }).to_string()
+                    "dependency"
                 } else {
-                    serde_json::json!({
-                        "valid": false,
-                        "error": error_message,
-                        "errorType": "parsing"
-                    }).to_string()
-                }
+                    "parsing"
+                };
+                let error = if error_type == "dependency" {
+                    format!("Dependency error: {}", error_message)
+                } else {
+                    error_message.clone()
+                };
+
+                serde_json::json!({
+                    "valid": false,
+                    "error": error,
+                    "errorType": error_type,
+                    "diagnostics": [diagnostic_from_error(content, &error_message, error_type)],
+                }).to_string()
             }
         }
     }
@@ -602,4 +1513,82 @@ world test-world {
         let result = wit_bindgen.validate_wit_syntax(valid_content);
         assert!(result, "Should validate sized list syntax as valid");
     }
+
+    #[test]
+    fn test_validate_wit_syntax_detailed_reports_diagnostics() {
+        let wit_bindgen = WitBindgen::new();
+
+        let result = wit_bindgen.validate_wit_syntax_detailed("");
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(parsed["valid"], false);
+        let diagnostics = parsed["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], "error");
+        assert_eq!(diagnostics[0]["line"], 1);
+        assert_eq!(diagnostics[0]["column"], 1);
+    }
+
+    #[test]
+    fn test_extract_symbols_outlines_interfaces_and_worlds() {
+        let wit_bindgen = WitBindgen::new();
+
+        let content = r#"package foo:bar;
+
+interface types {
+  record point {
+    x: u32,
+    y: u32,
+  }
+
+  resource counter {
+    constructor();
+    increment: func() -> u32;
+  }
+
+  get-origin: func() -> point;
+}
+
+world app {
+  import types;
+  export run: func();
+}"#;
+
+        let result = wit_bindgen.extract_symbols(content);
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        let interfaces = parsed["interfaces"].as_array().unwrap();
+        assert_eq!(interfaces.len(), 1);
+        assert_eq!(interfaces[0]["name"], "types");
+
+        let types = interfaces[0]["types"].as_array().unwrap();
+        let point = types.iter().find(|t| t["name"] == "point").unwrap();
+        assert_eq!(point["kind"], "record");
+        assert_eq!(point["fields"].as_array().unwrap().len(), 2);
+
+        let counter = types.iter().find(|t| t["name"] == "counter").unwrap();
+        assert_eq!(counter["kind"], "resource");
+        assert_eq!(counter["methods"].as_array().unwrap().len(), 2);
+
+        let functions = interfaces[0]["functions"].as_array().unwrap();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0]["name"], "get-origin");
+
+        let worlds = parsed["worlds"].as_array().unwrap();
+        assert_eq!(worlds.len(), 1);
+        assert_eq!(worlds[0]["name"], "app");
+        assert_eq!(worlds[0]["imports"].as_array().unwrap().len(), 1);
+        assert_eq!(worlds[0]["exports"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_extract_symbols_on_invalid_content_returns_empty_outline() {
+        let wit_bindgen = WitBindgen::new();
+
+        let result = wit_bindgen.extract_symbols("not valid wit");
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(parsed["interfaces"].as_array().unwrap().len(), 0);
+        assert_eq!(parsed["worlds"].as_array().unwrap().len(), 0);
+    }
 }